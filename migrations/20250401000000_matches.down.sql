@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_matches_user_b_id;
+DROP INDEX IF EXISTS idx_matches_user_a_id;
+
+DROP TABLE IF EXISTS Matches;
@@ -0,0 +1,21 @@
+-- Matches are formed when two users like each other. Each pair is stored once,
+-- with the bytewise smaller id in user_a_id. Ids are compared with the "C" collation so
+-- the order doesn't depend on the database locale and agrees with the server's.
+CREATE TABLE Matches (
+    id SERIAL PRIMARY KEY,
+    user_a_id VARCHAR(128) NOT NULL REFERENCES Users(id) ON DELETE CASCADE,
+    user_b_id VARCHAR(128) NOT NULL REFERENCES Users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(user_a_id, user_b_id),
+    CHECK (user_a_id::text COLLATE "C" < user_b_id::text COLLATE "C")
+);
+
+CREATE INDEX idx_matches_user_a_id ON Matches(user_a_id);
+CREATE INDEX idx_matches_user_b_id ON Matches(user_b_id);
+
+-- Backfill matches for likes that are already reciprocal
+INSERT INTO Matches (user_a_id, user_b_id, created_at)
+SELECT l1.user_id, l1.target_user_id, GREATEST(l1.created_at, l2.created_at)
+FROM UserLikes l1
+JOIN UserLikes l2 ON l1.user_id = l2.target_user_id AND l1.target_user_id = l2.user_id
+WHERE l1.user_id COLLATE "C" < l1.target_user_id COLLATE "C";
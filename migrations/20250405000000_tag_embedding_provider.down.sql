@@ -0,0 +1 @@
+ALTER TABLE Tags DROP COLUMN IF EXISTS embedding_provider;
@@ -0,0 +1,4 @@
+-- Track which embedding provider produced each tag embedding so tags can be
+-- re-embedded when the configured provider changes. Existing rows are left NULL,
+-- which marks them as stale and gets them re-embedded on the next startup.
+ALTER TABLE Tags ADD COLUMN embedding_provider VARCHAR(64);
@@ -0,0 +1,3 @@
+-- Roles gate privileged operations such as overriding nemesis ranking
+ALTER TABLE Users ADD COLUMN role VARCHAR(16) NOT NULL DEFAULT 'user'
+    CHECK (role IN ('user', 'admin'));
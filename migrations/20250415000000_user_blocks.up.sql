@@ -0,0 +1,12 @@
+-- A block hides both users from each other everywhere, regardless of who blocked whom
+CREATE TABLE UserBlocks (
+    id SERIAL PRIMARY KEY,
+    user_id VARCHAR(128) NOT NULL REFERENCES Users(id) ON DELETE CASCADE,
+    target_user_id VARCHAR(128) NOT NULL REFERENCES Users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE(user_id, target_user_id),
+    CHECK (user_id::text != target_user_id::text)
+);
+
+CREATE INDEX idx_userblocks_user_id ON UserBlocks(user_id);
+CREATE INDEX idx_userblocks_target_user_id ON UserBlocks(target_user_id);
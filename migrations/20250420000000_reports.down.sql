@@ -0,0 +1,3 @@
+ALTER TABLE Users DROP COLUMN IF EXISTS suspended_until;
+DROP TABLE IF EXISTS UserWarnings;
+DROP TABLE IF EXISTS Reports;
@@ -0,0 +1,38 @@
+-- Reports filed by users against other users, worked through by admins.
+-- Both users are kept nullable so reports survive account deletion.
+CREATE TABLE Reports (
+    id SERIAL PRIMARY KEY,
+    reporter_id VARCHAR(128) REFERENCES Users(id) ON DELETE SET NULL,
+    reported_user_id VARCHAR(128) REFERENCES Users(id) ON DELETE SET NULL,
+    reason VARCHAR(32) NOT NULL
+        CHECK (reason IN ('harassment', 'spam', 'impersonation', 'inappropriate_content', 'hate_speech', 'other')),
+    details TEXT NOT NULL DEFAULT '',
+    status VARCHAR(16) NOT NULL DEFAULT 'open'
+        CHECK (status IN ('open', 'triaged', 'resolved', 'dismissed')),
+    action VARCHAR(16) CHECK (action IN ('warn', 'suspend', 'delete')),
+    moderator_notes TEXT,
+    handled_by VARCHAR(128) REFERENCES Users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    resolved_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_reports_status_created_at ON Reports(status, created_at);
+CREATE INDEX idx_reports_reported_user_id ON Reports(reported_user_id);
+
+-- A user can only have one pending report against the same user
+CREATE UNIQUE INDEX idx_reports_pending_unique ON Reports(reporter_id, reported_user_id)
+    WHERE status IN ('open', 'triaged');
+
+CREATE TABLE UserWarnings (
+    id SERIAL PRIMARY KEY,
+    user_id VARCHAR(128) NOT NULL REFERENCES Users(id) ON DELETE CASCADE,
+    report_id INTEGER REFERENCES Reports(id) ON DELETE SET NULL,
+    message TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_userwarnings_user_id ON UserWarnings(user_id);
+
+-- Suspended users are hidden from everyone else and can't interact until this passes
+ALTER TABLE Users ADD COLUMN suspended_until TIMESTAMPTZ;
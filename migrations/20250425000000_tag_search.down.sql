@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_tags_name_lower_prefix;
+DROP INDEX IF EXISTS idx_tags_name_trgm;
@@ -0,0 +1,6 @@
+-- Fuzzy tag search
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX idx_tags_name_trgm ON Tags USING gin (name gin_trgm_ops);
+-- Serves case-insensitive prefix matches
+CREATE INDEX idx_tags_name_lower_prefix ON Tags (LOWER(name) text_pattern_ops);
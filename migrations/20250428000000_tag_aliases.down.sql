@@ -0,0 +1,3 @@
+-- Case-folded tag names are not restored
+ALTER TABLE Tags DROP CONSTRAINT IF EXISTS tags_name_lowercase;
+DROP TABLE IF EXISTS TagAliases;
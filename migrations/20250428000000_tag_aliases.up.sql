@@ -0,0 +1,40 @@
+-- Tag names are case-folded, and variants can be aliased to a canonical tag
+CREATE TABLE TagAliases (
+    alias VARCHAR(255) PRIMARY KEY,
+    tag_name VARCHAR(255) NOT NULL REFERENCES Tags(name) ON DELETE CASCADE ON UPDATE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (alias::text = LOWER(alias)),
+    CHECK (alias::text != tag_name::text)
+);
+
+CREATE INDEX idx_tagaliases_tag_name ON TagAliases(tag_name);
+
+-- Fold existing tags to lowercase. The lowercase tags start without an embedding and are
+-- embedded again on startup.
+INSERT INTO Tags (name)
+SELECT DISTINCT LOWER(name) FROM Tags WHERE name != LOWER(name)
+ON CONFLICT (name) DO NOTHING;
+
+-- Keep the oldest of the rows that only differ by case, then lowercase the rest
+DELETE FROM UserTags ut
+USING UserTags o
+WHERE o.user_id = ut.user_id
+    AND LOWER(o.tag_name) = LOWER(ut.tag_name)
+    AND o.id < ut.id;
+
+UPDATE UserTags SET tag_name = LOWER(tag_name) WHERE tag_name != LOWER(tag_name);
+
+DELETE FROM UserDislikeTags udt
+USING UserDislikeTags o
+WHERE o.user_id = udt.user_id
+    AND o.target_user_id = udt.target_user_id
+    AND LOWER(o.tag_name) = LOWER(udt.tag_name)
+    AND o.id < udt.id;
+
+UPDATE UserDislikeTags SET tag_name = LOWER(tag_name) WHERE tag_name != LOWER(tag_name);
+
+DELETE FROM Tags WHERE name != LOWER(name);
+
+ALTER TABLE Tags ADD CONSTRAINT tags_name_lowercase CHECK (name::text = LOWER(name));
+
+REFRESH MATERIALIZED VIEW tag_counts;
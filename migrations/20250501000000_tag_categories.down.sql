@@ -0,0 +1,6 @@
+DROP VIEW IF EXISTS tag_effective_categories;
+ALTER TABLE Tags
+    DROP CONSTRAINT IF EXISTS tags_parent_not_self,
+    DROP COLUMN IF EXISTS parent_name,
+    DROP COLUMN IF EXISTS category;
+DROP TABLE IF EXISTS TagCategories;
@@ -0,0 +1,40 @@
+-- Tags can belong to a category and have a parent tag, e.g. neovim -> vim -> programming
+CREATE TABLE TagCategories (
+    name VARCHAR(64) PRIMARY KEY,
+    description TEXT NOT NULL DEFAULT '',
+    CHECK (name::text = LOWER(name))
+);
+
+INSERT INTO TagCategories (name, description) VALUES
+    ('programming', 'Languages, editors, tools and holy wars of software development'),
+    ('food', 'Dishes, cuisines, diets and pineapple on pizza'),
+    ('politics', 'Political views, causes and ideologies'),
+    ('music', 'Genres, artists and instruments'),
+    ('sports', 'Teams, sports and ways to work out'),
+    ('entertainment', 'Movies, shows, books and games'),
+    ('lifestyle', 'Habits, hobbies and personality traits');
+
+ALTER TABLE Tags
+    ADD COLUMN category VARCHAR(64) REFERENCES TagCategories(name) ON UPDATE CASCADE ON DELETE SET NULL,
+    ADD COLUMN parent_name VARCHAR(255) REFERENCES Tags(name) ON UPDATE CASCADE ON DELETE SET NULL,
+    ADD CONSTRAINT tags_parent_not_self CHECK (parent_name::text != name::text);
+
+CREATE INDEX idx_tags_category ON Tags(category);
+CREATE INDEX idx_tags_parent_name ON Tags(parent_name);
+
+-- Category of every tag, inherited from the closest ancestor when a tag has none of its own
+CREATE VIEW tag_effective_categories AS
+WITH RECURSIVE chain AS (
+    SELECT name, category, parent_name, 0 AS depth
+    FROM Tags
+    UNION ALL
+    SELECT chain.name, parent.category, parent.parent_name, chain.depth + 1
+    FROM chain
+    JOIN Tags parent ON parent.name = chain.parent_name
+    -- Parent cycles are rejected on write, the depth limit only guards against bad data
+    WHERE chain.category IS NULL AND chain.depth < 16
+)
+SELECT DISTINCT ON (name) name, category
+FROM chain
+WHERE category IS NOT NULL
+ORDER BY name, depth;
@@ -0,0 +1,3 @@
+DROP TRIGGER IF EXISTS usertags_track_adoption ON UserTags;
+DROP FUNCTION IF EXISTS track_tag_adoption();
+DROP TABLE IF EXISTS TagAdoptionsHourly;
@@ -0,0 +1,37 @@
+-- Hourly rollup of tag adoptions, so trending tags don't have to scan UserTags
+CREATE TABLE TagAdoptionsHourly (
+    tag_name VARCHAR(255) NOT NULL REFERENCES Tags(name) ON DELETE CASCADE,
+    hour TIMESTAMPTZ NOT NULL,
+    -- Users who added the tag during the hour and still hold it
+    adopters INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (tag_name, hour)
+);
+
+CREATE INDEX idx_tagadoptionshourly_hour ON TagAdoptionsHourly(hour);
+
+INSERT INTO TagAdoptionsHourly (tag_name, hour, adopters)
+SELECT tag_name, date_trunc('hour', created_at), COUNT(*)
+FROM UserTags
+GROUP BY tag_name, date_trunc('hour', created_at);
+
+-- Keep the rollup in step with UserTags, removed tags no longer count as adopted
+CREATE FUNCTION track_tag_adoption() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        INSERT INTO TagAdoptionsHourly (tag_name, hour, adopters)
+        VALUES (NEW.tag_name, date_trunc('hour', NEW.created_at), 1)
+        ON CONFLICT (tag_name, hour)
+        DO UPDATE SET adopters = TagAdoptionsHourly.adopters + 1;
+        RETURN NEW;
+    ELSE
+        UPDATE TagAdoptionsHourly
+        SET adopters = adopters - 1
+        WHERE tag_name = OLD.tag_name AND hour = date_trunc('hour', OLD.created_at);
+        RETURN OLD;
+    END IF;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER usertags_track_adoption
+AFTER INSERT OR DELETE ON UserTags
+FOR EACH ROW EXECUTE FUNCTION track_tag_adoption();
@@ -0,0 +1,11 @@
+DROP TRIGGER IF EXISTS usertags_track_count ON UserTags;
+DROP FUNCTION IF EXISTS track_tag_count();
+DROP TABLE IF EXISTS tag_counts;
+
+CREATE MATERIALIZED VIEW tag_counts AS
+SELECT tag_name, COUNT(*) as user_count
+FROM UserTags
+GROUP BY tag_name
+ORDER BY user_count DESC;
+
+CREATE UNIQUE INDEX ON tag_counts(tag_name);
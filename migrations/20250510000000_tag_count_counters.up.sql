@@ -0,0 +1,41 @@
+-- Replace the tag_counts materialized view with counters maintained by a trigger on UserTags,
+-- so tag edits no longer refresh (and lock) the whole view
+DROP MATERIALIZED VIEW tag_counts;
+
+CREATE TABLE tag_counts (
+    tag_name VARCHAR(255) PRIMARY KEY REFERENCES Tags(name) ON DELETE CASCADE,
+    user_count BIGINT NOT NULL CHECK (user_count >= 0)
+);
+
+CREATE INDEX idx_tag_counts_user_count ON tag_counts(user_count DESC);
+
+INSERT INTO tag_counts (tag_name, user_count)
+SELECT tag_name, COUNT(*)
+FROM UserTags
+GROUP BY tag_name;
+
+-- Only the counter of the edited tag is locked, so edits of different tags run concurrently
+CREATE FUNCTION track_tag_count() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        INSERT INTO tag_counts (tag_name, user_count)
+        VALUES (NEW.tag_name, 1)
+        ON CONFLICT (tag_name)
+        DO UPDATE SET user_count = tag_counts.user_count + 1;
+        RETURN NEW;
+    ELSE
+        UPDATE tag_counts
+        SET user_count = user_count - 1
+        WHERE tag_name = OLD.tag_name;
+
+        -- Tags nobody holds are not counted, like in the materialized view
+        DELETE FROM tag_counts
+        WHERE tag_name = OLD.tag_name AND user_count = 0;
+        RETURN OLD;
+    END IF;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER usertags_track_count
+AFTER INSERT OR DELETE ON UserTags
+FOR EACH ROW EXECUTE FUNCTION track_tag_count();
@@ -0,0 +1,3 @@
+ALTER TABLE UserTags
+    DROP CONSTRAINT IF EXISTS usertags_stance_range,
+    DROP COLUMN IF EXISTS stance;
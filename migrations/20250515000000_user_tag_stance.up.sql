@@ -0,0 +1,4 @@
+-- How strongly a user is for (positive) or against (negative) each of their tags
+ALTER TABLE UserTags
+    ADD COLUMN stance SMALLINT NOT NULL DEFAULT 1,
+    ADD CONSTRAINT usertags_stance_range CHECK (stance BETWEEN -3 AND 3 AND stance != 0);
@@ -0,0 +1,18 @@
+-- Tags related to each other through the users holding them, rebuilt periodically from
+-- UserTags and UserLikes.
+-- related: tags held together by the same users
+-- opposed: tags held by users who liked holders of the tag
+CREATE TABLE TagRelations (
+    tag_name VARCHAR(255) NOT NULL REFERENCES Tags(name) ON DELETE CASCADE,
+    other_tag_name VARCHAR(255) NOT NULL REFERENCES Tags(name) ON DELETE CASCADE,
+    kind VARCHAR(16) NOT NULL CHECK (kind IN ('related', 'opposed')),
+    -- Users backing the relation
+    users INTEGER NOT NULL,
+    -- How much more often than chance the tags go together, and its log
+    lift DOUBLE PRECISION NOT NULL,
+    pmi DOUBLE PRECISION NOT NULL,
+    computed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (tag_name, kind, other_tag_name)
+);
+
+CREATE INDEX idx_tagrelations_lookup ON TagRelations(tag_name, kind, pmi DESC);
@@ -0,0 +1,8 @@
+-- Tag names admins banned, rejected whenever a tag name is validated
+CREATE TABLE BannedTags (
+    name VARCHAR(255) PRIMARY KEY,
+    reason TEXT NOT NULL DEFAULT '',
+    banned_by VARCHAR(128) REFERENCES Users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (name::text = LOWER(name))
+);
@@ -0,0 +1,11 @@
+-- Usernames are unique regardless of case. Accounts sharing a username with an older one get
+-- their user ID appended so the index can be built.
+UPDATE Users u
+SET username = LEFT(u.username, 100) || '_' || u.id
+FROM (
+    SELECT id, ROW_NUMBER() OVER (PARTITION BY LOWER(username) ORDER BY created_at, id) AS n
+    FROM Users
+) d
+WHERE u.id = d.id AND d.n > 1;
+
+CREATE UNIQUE INDEX idx_users_username_lower ON Users (LOWER(username));
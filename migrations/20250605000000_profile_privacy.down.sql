@@ -0,0 +1,4 @@
+ALTER TABLE Users
+    DROP COLUMN IF EXISTS display_name_visibility,
+    DROP COLUMN IF EXISTS avatar_url_visibility,
+    DROP COLUMN IF EXISTS bio_visibility;
@@ -0,0 +1,8 @@
+-- Who may see each optional profile field: 'everyone', or only 'matches'
+ALTER TABLE Users
+    ADD COLUMN display_name_visibility VARCHAR(16) NOT NULL DEFAULT 'everyone'
+        CHECK (display_name_visibility IN ('everyone', 'matches')),
+    ADD COLUMN avatar_url_visibility VARCHAR(16) NOT NULL DEFAULT 'everyone'
+        CHECK (avatar_url_visibility IN ('everyone', 'matches')),
+    ADD COLUMN bio_visibility VARCHAR(16) NOT NULL DEFAULT 'everyone'
+        CHECK (bio_visibility IN ('everyone', 'matches'));
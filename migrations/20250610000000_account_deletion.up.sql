@@ -0,0 +1,14 @@
+-- When the account is deleted for good, unless the user cancels before then
+ALTER TABLE Users ADD COLUMN deletion_scheduled_at TIMESTAMPTZ;
+
+CREATE INDEX idx_users_deletion_scheduled_at ON Users(deletion_scheduled_at)
+    WHERE deletion_scheduled_at IS NOT NULL;
+
+-- Objects users uploaded to the bucket, removed along with their account
+CREATE TABLE StorageObjects (
+    name VARCHAR(255) PRIMARY KEY,
+    user_id VARCHAR(128) NOT NULL REFERENCES Users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_storageobjects_user_id ON StorageObjects(user_id);
@@ -0,0 +1 @@
+DROP VIEW IF EXISTS user_tag_oppositions;
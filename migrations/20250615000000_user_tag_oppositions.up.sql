@@ -0,0 +1,23 @@
+-- How strongly each tag of a user opposes each tag of another user, shared by nemesis scoring
+-- and the opposing tags it reports so both agree.
+-- Cosine distance is 0 for identical and 2 for opposite tags, scaled to 0-1 where 1 = perfect
+-- opposite. Being against a tag flips it, so opposite stances on the same tag are perfect
+-- opposites. Pairs with an uncategorized tag count as cross-category.
+CREATE VIEW user_tag_oppositions AS
+SELECT
+    ut.user_id,
+    ut.tag_name,
+    ot.user_id AS other_user_id,
+    ot.tag_name AS other_tag_name,
+    CASE
+        WHEN (ut.stance > 0) = (ot.stance > 0) THEN (t.embedding <=> o.embedding) / 2
+        ELSE 1 - (t.embedding <=> o.embedding) / 2
+    END AS opposition,
+    COALESCE(tc.category = oc.category, FALSE) AS same_category
+FROM UserTags ut
+JOIN Tags t ON t.name = ut.tag_name
+JOIN UserTags ot ON ot.user_id != ut.user_id
+JOIN Tags o ON o.name = ot.tag_name
+LEFT JOIN tag_effective_categories tc ON tc.name = t.name
+LEFT JOIN tag_effective_categories oc ON oc.name = o.name
+WHERE t.embedding IS NOT NULL AND o.embedding IS NOT NULL;
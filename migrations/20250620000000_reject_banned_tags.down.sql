@@ -0,0 +1,2 @@
+DROP TRIGGER IF EXISTS tags_reject_banned ON Tags;
+DROP FUNCTION IF EXISTS reject_banned_tag();
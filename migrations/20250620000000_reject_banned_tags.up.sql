@@ -0,0 +1,15 @@
+-- Reject banned names whenever a tag is created, so every server instance sees a ban as soon
+-- as it commits
+CREATE FUNCTION reject_banned_tag() RETURNS TRIGGER AS $$
+BEGIN
+    IF EXISTS (SELECT 1 FROM BannedTags WHERE name = NEW.name) THEN
+        RAISE EXCEPTION 'Tag name % is banned', NEW.name
+            USING ERRCODE = 'check_violation', CONSTRAINT = 'tags_name_not_banned';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER tags_reject_banned
+BEFORE INSERT OR UPDATE OF name ON Tags
+FOR EACH ROW EXECUTE FUNCTION reject_banned_tag();
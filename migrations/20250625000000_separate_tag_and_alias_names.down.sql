@@ -0,0 +1,5 @@
+DROP TRIGGER IF EXISTS tag_aliases_reject_tag ON TagAliases;
+DROP FUNCTION IF EXISTS reject_tag_alias();
+DROP TRIGGER IF EXISTS tags_reject_aliased ON Tags;
+DROP FUNCTION IF EXISTS reject_aliased_tag();
+DROP FUNCTION IF EXISTS lock_tag_name(TEXT);
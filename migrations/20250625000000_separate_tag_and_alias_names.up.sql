@@ -0,0 +1,37 @@
+-- A name is either a tag or an alias of one, never both. Writers of either table take a lock
+-- on the name first, so concurrent transactions see each other's rows before checking.
+CREATE FUNCTION lock_tag_name(name TEXT) RETURNS VOID AS $$
+BEGIN
+    PERFORM pg_advisory_xact_lock(hashtext('tag_name'), hashtext(name));
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE FUNCTION reject_aliased_tag() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM lock_tag_name(NEW.name);
+    IF EXISTS (SELECT 1 FROM TagAliases WHERE alias = NEW.name) THEN
+        RAISE EXCEPTION 'Tag name % is an alias', NEW.name
+            USING ERRCODE = 'unique_violation', CONSTRAINT = 'tags_name_not_alias';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER tags_reject_aliased
+BEFORE INSERT OR UPDATE OF name ON Tags
+FOR EACH ROW EXECUTE FUNCTION reject_aliased_tag();
+
+CREATE FUNCTION reject_tag_alias() RETURNS TRIGGER AS $$
+BEGIN
+    PERFORM lock_tag_name(NEW.alias);
+    IF EXISTS (SELECT 1 FROM Tags WHERE name = NEW.alias) THEN
+        RAISE EXCEPTION 'Alias % is a tag', NEW.alias
+            USING ERRCODE = 'unique_violation', CONSTRAINT = 'tag_aliases_alias_not_tag';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER tag_aliases_reject_tag
+BEFORE INSERT OR UPDATE OF alias ON TagAliases
+FOR EACH ROW EXECUTE FUNCTION reject_tag_alias();
@@ -0,0 +1 @@
+DROP FUNCTION IF EXISTS user_is_active(Users);
@@ -0,0 +1,4 @@
+-- Whether a user is shown to other users at all. Suspended users are hidden from everyone.
+CREATE FUNCTION user_is_active(u Users) RETURNS BOOLEAN AS $$
+    SELECT u.suspended_until IS NULL OR u.suspended_until <= NOW()
+$$ LANGUAGE sql STABLE;
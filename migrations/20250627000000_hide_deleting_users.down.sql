@@ -0,0 +1,3 @@
+CREATE OR REPLACE FUNCTION user_is_active(u Users) RETURNS BOOLEAN AS $$
+    SELECT u.suspended_until IS NULL OR u.suspended_until <= NOW()
+$$ LANGUAGE sql STABLE;
@@ -0,0 +1,5 @@
+-- Accounts in their deletion grace period are hidden from everyone too
+CREATE OR REPLACE FUNCTION user_is_active(u Users) RETURNS BOOLEAN AS $$
+    SELECT (u.suspended_until IS NULL OR u.suspended_until <= NOW())
+        AND u.deletion_scheduled_at IS NULL
+$$ LANGUAGE sql STABLE;
@@ -7,6 +7,10 @@ use axum::{
 use serde::Serialize;
 use tracing::error;
 
+/// Users whose ID starts with this prefix are created with the admin role, so integration
+/// tests can exercise the admin routes.
+pub const ADMIN_PREFIX: &str = "dummy-admin-";
+
 /// Represents an authenticated user, extracted from the Authentication header.
 #[derive(Debug, Clone)]
 pub struct AuthUser {
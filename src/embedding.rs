@@ -0,0 +1,181 @@
+use pgvector::Vector;
+use std::sync::Arc;
+
+/// Dimension of every embedding stored in the database (`vector(384)`).
+///
+/// This matches the output size of all-MiniLM-L6-v2, so a real text model can be dropped
+/// in later without a schema change.
+pub const EMBEDDING_DIM: usize = 384;
+
+/// Turns text (tag names) into embedding vectors.
+///
+/// The provider is selected once at startup and shared through
+/// [`ArchenemyState`](crate::types::ArchenemyState). Implementations must be deterministic:
+/// the same text always has to produce the same vector, otherwise stored tag embeddings and
+/// freshly computed ones would drift apart.
+pub trait EmbeddingProvider: Send + Sync {
+    /// Stable identifier persisted next to each tag embedding.
+    ///
+    /// When the configured provider changes, tags embedded by a different provider are
+    /// detected through this name and re-embedded on startup.
+    fn name(&self) -> &'static str;
+
+    /// Embed a piece of text into a unit-length vector of [`EMBEDDING_DIM`] dimensions.
+    fn embed(&self, text: &str) -> Vector;
+}
+
+/// Shared handle to the configured embedding provider.
+pub type SharedEmbeddingProvider = Arc<dyn EmbeddingProvider>;
+
+/// Name of the provider used when none is configured.
+pub const DEFAULT_PROVIDER: &str = NgramEmbedding::NAME;
+
+/// Look up an embedding provider by its configured name.
+///
+/// Returns `None` if the name is unknown.
+pub fn provider_from_name(name: &str) -> Option<SharedEmbeddingProvider> {
+    match name {
+        NgramEmbedding::NAME => Some(Arc::new(NgramEmbedding::default())),
+        _ => None,
+    }
+}
+
+/// Offline embedding based on character n-gram feature hashing.
+///
+/// Each word of the input is padded with boundary markers (`<rust>`) and split into
+/// character n-grams. Every n-gram is hashed with FNV-1a into one of the
+/// [`EMBEDDING_DIM`] buckets, with one bit of the hash choosing the sign so collisions
+/// tend to cancel out instead of piling up. The result is L2-normalized.
+///
+/// Tags that share spelling share n-grams, so `rust` and `rustlang` end up close to each
+/// other, while the hash is stable across Rust releases and platforms.
+#[derive(Debug, Clone)]
+pub struct NgramEmbedding {
+    /// Smallest n-gram length extracted from each word
+    min_n: usize,
+    /// Largest n-gram length extracted from each word
+    max_n: usize,
+}
+
+impl NgramEmbedding {
+    pub const NAME: &'static str = "ngram";
+}
+
+impl Default for NgramEmbedding {
+    fn default() -> Self {
+        Self { min_n: 3, max_n: 5 }
+    }
+}
+
+impl EmbeddingProvider for NgramEmbedding {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn embed(&self, text: &str) -> Vector {
+        let mut values = vec![0.0f32; EMBEDDING_DIM];
+
+        let lowered = text.to_lowercase();
+        for word in lowered.split(|c: char| !c.is_alphanumeric()) {
+            if word.is_empty() {
+                continue;
+            }
+
+            let padded: Vec<char> = std::iter::once('<')
+                .chain(word.chars())
+                .chain(std::iter::once('>'))
+                .collect();
+
+            // The whole word is a feature of its own, so exact matches weigh more
+            let mut grams: Vec<String> = vec![padded.iter().collect()];
+            for n in self.min_n..=self.max_n {
+                grams.extend(padded.windows(n).map(|w| w.iter().collect::<String>()));
+            }
+
+            for gram in grams {
+                let hash = fnv1a(gram.as_bytes());
+                let index = (hash % EMBEDDING_DIM as u64) as usize;
+                let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+                values[index] += sign;
+            }
+        }
+
+        normalize(values)
+    }
+}
+
+/// Scale a vector to unit length so cosine similarity behaves.
+///
+/// A zero vector is returned unchanged.
+pub fn normalize(values: Vec<f32>) -> Vector {
+    let magnitude: f32 = values.iter().map(|&val| val * val).sum::<f32>().sqrt();
+
+    if magnitude > 0.0 {
+        Vector::from(
+            values
+                .iter()
+                .map(|&val| val / magnitude)
+                .collect::<Vec<_>>(),
+        )
+    } else {
+        Vector::from(values)
+    }
+}
+
+/// 64-bit FNV-1a, used because it is stable across builds unlike `DefaultHasher`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cosine(embedder: &NgramEmbedding, a: &str, b: &str) -> f32 {
+        let a = embedder.embed(a);
+        let b = embedder.embed(b);
+        a.as_slice()
+            .iter()
+            .zip(b.as_slice())
+            .map(|(x, y)| x * y)
+            .sum()
+    }
+
+    #[test]
+    fn ngram_embeddings_are_unit_length_and_deterministic() {
+        let embedder = NgramEmbedding::default();
+        let embedding = embedder.embed("rust");
+
+        assert_eq!(embedding.as_slice().len(), EMBEDDING_DIM);
+        assert!((cosine(&embedder, "rust", "rust") - 1.0).abs() < 1e-5);
+        assert_eq!(embedding, embedder.embed("rust"));
+    }
+
+    #[test]
+    fn ngram_embeddings_place_shared_spellings_closer() {
+        let embedder = NgramEmbedding::default();
+
+        let related = cosine(&embedder, "rust", "rustlang");
+        for unrelated in ["pineapple", "gardening", "jazz"] {
+            assert!(
+                related > cosine(&embedder, "rust", unrelated),
+                "rust should be nearer rustlang than {}",
+                unrelated
+            );
+        }
+        assert!(cosine(&embedder, "kayaking", "kayak") > cosine(&embedder, "kayaking", "knitting"));
+    }
+
+    #[test]
+    fn ngram_embeddings_ignore_case_and_punctuation() {
+        let embedder = NgramEmbedding::default();
+
+        assert_eq!(embedder.embed("Rust"), embedder.embed("rust"));
+        assert_eq!(embedder.embed("rust-lang"), embedder.embed("rust lang"));
+    }
+}
@@ -1,3 +1,4 @@
+pub mod embedding;
 pub mod storage;
 pub mod types;
 pub mod user;
@@ -10,4 +11,5 @@ pub mod tags {
     pub static USER: &str = "User";
     pub static TAGS: &str = "Tags";
     pub static STORAGE: &str = "Storage";
+    pub static MODERATION: &str = "Moderation";
 }
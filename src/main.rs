@@ -1,9 +1,13 @@
+use archenemy::embedding::{self, SharedEmbeddingProvider};
 use archenemy::types::ArchenemyState;
+use archenemy::user::{RankingConfig, Validate};
 use axum::Router;
 use firebase_auth::{FirebaseAuth, FirebaseAuthState};
+use s3::Bucket;
 
-use sqlx::postgres::PgPoolOptions;
+use sqlx::postgres::{PgPool, PgPoolOptions};
 use std::sync::Arc;
+use std::time::Duration;
 use utoipa::{
     openapi::security::{ApiKey, ApiKeyValue, SecurityScheme},
     Modify, OpenApi,
@@ -12,7 +16,7 @@ use utoipa_axum::router::OpenApiRouter;
 use utoipa_swagger_ui::SwaggerUi;
 
 #[cfg(not(feature = "local"))]
-use shuttle_runtime::SecretStore;
+use shuttle_runtime::{tokio, SecretStore};
 
 #[cfg(feature = "local")]
 mod local {
@@ -46,7 +50,8 @@ mod local {
     tags(
         (name = archenemy::tags::USER, description = "User related endpoints"),
         (name = archenemy::tags::TAGS, description = "Tag related endpoints"),
-        (name = archenemy::tags::STORAGE, description = "Storage related endpoints")
+        (name = archenemy::tags::STORAGE, description = "Storage related endpoints"),
+        (name = archenemy::tags::MODERATION, description = "Admin only moderation and maintenance endpoints")
     ),
 )]
 struct ApiDoc;
@@ -64,16 +69,139 @@ impl Modify for SecurityAddon {
     }
 }
 
+// Parse the optional RANKING_CONFIG secret, a JSON object with any RankingConfig fields
+fn ranking_config(raw: Option<String>) -> RankingConfig {
+    let config = match raw {
+        Some(raw) => serde_json::from_str(&raw).expect("RANKING_CONFIG is not valid JSON"),
+        None => RankingConfig::default(),
+    };
+
+    if let Err(e) = config.validate() {
+        panic!("Invalid RANKING_CONFIG: {}", e);
+    }
+
+    config
+}
+
+// Parse the optional TAG_RELATIONS_REFRESH_MINUTES secret, hourly by default
+fn tag_relations_refresh(raw: Option<String>) -> Duration {
+    let minutes = match raw {
+        Some(raw) => raw
+            .parse::<u64>()
+            .ok()
+            .filter(|&minutes| minutes > 0)
+            .expect("TAG_RELATIONS_REFRESH_MINUTES must be a positive number"),
+        None => 60,
+    };
+
+    Duration::from_secs(minutes * 60)
+}
+
+// Rebuild the related and opposed tags on startup, then every `period`
+fn spawn_tag_relations_refresher(pool: PgPool, period: Duration) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            match archenemy::user::rebuild_tag_relations(
+                &pool,
+                archenemy::user::MIN_TAG_RELATION_USERS,
+            )
+            .await
+            {
+                Ok((related, opposed)) => println!(
+                    "Tag relations rebuilt ({} related, {} opposed pairs)",
+                    related, opposed
+                ),
+                Err(e) => eprintln!("Failed to rebuild tag relations: {:?}", e),
+            }
+        }
+    });
+}
+
+// Parse the optional ACCOUNT_DELETION_GRACE_MINUTES secret, 30 days by default
+fn account_deletion_grace(raw: Option<String>) -> Duration {
+    match raw {
+        Some(raw) => raw
+            .parse::<u64>()
+            .ok()
+            .and_then(|minutes| minutes.checked_mul(60))
+            .map(Duration::from_secs)
+            .expect("ACCOUNT_DELETION_GRACE_MINUTES must be a number of minutes"),
+        None => archenemy::user::DEFAULT_ACCOUNT_DELETION_GRACE_PERIOD,
+    }
+}
+
+// How often accounts past their deletion grace period are looked for
+const ACCOUNT_PURGE_PERIOD: Duration = Duration::from_secs(60 * 60);
+
+// Delete accounts whose grace period is over, checking every `period`
+fn spawn_account_purger(pool: PgPool, bucket: Arc<Bucket>, period: Duration) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            match archenemy::user::purge_deleted_users(&pool, &bucket).await {
+                Ok((deleted, failed)) => {
+                    if deleted > 0 {
+                        println!("Deleted {} accounts past their grace period", deleted);
+                    }
+                    for (user_id, e) in failed {
+                        eprintln!("Failed to delete account {}: {:?}", user_id, e);
+                    }
+                }
+                Err(e) => eprintln!("Failed to delete accounts: {:?}", e),
+            }
+        }
+    });
+}
+
+// Settings read from the secrets, the same for shuttle and local runs
+struct AppConfig {
+    db_url: String,
+    firebase_auth_id: String,
+    storage_access_id: String,
+    storage_access_token: String,
+    embedder: SharedEmbeddingProvider,
+    ranking: RankingConfig,
+    relations_refresh: Duration,
+    deletion_grace: Duration,
+}
+
+fn app_config(secret: impl Fn(&str) -> Option<String>) -> AppConfig {
+    let embedding_provider =
+        secret("EMBEDDING_PROVIDER").unwrap_or_else(|| embedding::DEFAULT_PROVIDER.to_string());
+
+    AppConfig {
+        db_url: secret("NEON_POSTGRES_URL").expect("NEON_POSTGRES_URL not found"),
+        firebase_auth_id: secret("FIREBASE_AUTH_PROJECT_ID").expect("Secret not found"),
+        storage_access_id: secret("STORAGE_ACCESS_ID").expect("STORAGE_ACCESS_ID not found"),
+        storage_access_token: secret("STORAGE_ACCESS_TOKEN")
+            .expect("STORAGE_ACCESS_TOKEN not found"),
+        embedder: embedding::provider_from_name(&embedding_provider)
+            .expect("Unknown EMBEDDING_PROVIDER"),
+        ranking: ranking_config(secret("RANKING_CONFIG")),
+        relations_refresh: tag_relations_refresh(secret("TAG_RELATIONS_REFRESH_MINUTES")),
+        deletion_grace: account_deletion_grace(secret("ACCOUNT_DELETION_GRACE_MINUTES")),
+    }
+}
+
 // Create common setup function to be used by both local and shuttle
-async fn setup_app(
-    db_url: &str,
-    firebase_auth_id: &str,
-    storage_access_id: &str,
-    storage_access_token: &str,
-) -> Router {
+async fn setup_app(config: AppConfig) -> Router {
+    let AppConfig {
+        db_url,
+        firebase_auth_id,
+        storage_access_id,
+        storage_access_token,
+        embedder,
+        ranking,
+        relations_refresh,
+        deletion_grace,
+    } = config;
+
     let pool = PgPoolOptions::new()
         .max_connections(5)
-        .connect(db_url)
+        .connect(&db_url)
         .await
         .expect("Failed to connect to Postgres");
 
@@ -85,20 +213,37 @@ async fn setup_app(
         }
     };
 
-    let firebase_auth = Arc::new(FirebaseAuth::new(firebase_auth_id).await);
+    match archenemy::user::reembed_stale_tags(&pool, embedder.as_ref()).await {
+        Ok(count) => println!(
+            "Embeddings up to date with provider '{}' ({} tags re-embedded)",
+            embedder.name(),
+            count
+        ),
+        Err(e) => eprintln!("Failed to re-embed stale tags: {:?}", e),
+    };
+
+    println!("Ranking nemeses with {:?}", ranking);
+
+    spawn_tag_relations_refresher(pool.clone(), relations_refresh);
+
+    let bucket = archenemy::storage::bucket(&storage_access_id, &storage_access_token);
+    spawn_account_purger(pool.clone(), bucket.clone(), ACCOUNT_PURGE_PERIOD);
+
+    let firebase_auth = Arc::new(FirebaseAuth::new(&firebase_auth_id).await);
 
     let (router, api) = OpenApiRouter::with_openapi(ApiDoc::openapi())
         .nest(
             "/api/v1",
             OpenApiRouter::new()
-                .nest(
-                    "/storage",
-                    archenemy::storage::routes(storage_access_id, storage_access_token),
-                )
+                .nest("/storage", archenemy::storage::routes(bucket.clone()))
                 .merge(archenemy::user::routes())
                 .with_state(ArchenemyState {
                     auth: FirebaseAuthState { firebase_auth },
                     pool: pool.clone(),
+                    embedder,
+                    ranking,
+                    bucket,
+                    account_deletion_grace: deletion_grace,
                 }),
         )
         .split_for_parts();
@@ -109,29 +254,9 @@ async fn setup_app(
 #[cfg(not(feature = "local"))]
 #[shuttle_runtime::main]
 async fn main(#[shuttle_runtime::Secrets] secrets: SecretStore) -> shuttle_axum::ShuttleAxum {
-    let db_url = secrets
-        .get("NEON_POSTGRES_URL")
-        .expect("NEON_POSTGRES_URL not found");
-
-    let firebase_auth_id = secrets
-        .get("FIREBASE_AUTH_PROJECT_ID")
-        .expect("Secret not found");
-
-    let storage_access_id = secrets
-        .get("STORAGE_ACCESS_ID")
-        .expect("STORAGE_ACCESS_ID not found");
-
-    let storage_access_token = secrets
-        .get("STORAGE_ACCESS_TOKEN")
-        .expect("STORAGE_ACCESS_TOKEN not found");
-
-    let router = setup_app(
-        &db_url,
-        &firebase_auth_id,
-        &storage_access_id,
-        &storage_access_token,
-    )
-    .await;
+    let config = app_config(|key| secrets.get(key));
+
+    let router = setup_app(config).await;
 
     Ok(router.into())
 }
@@ -141,29 +266,9 @@ async fn main(#[shuttle_runtime::Secrets] secrets: SecretStore) -> shuttle_axum:
 async fn main() {
     let secrets = local::Secrets::new();
 
-    let db_url = secrets
-        .get("NEON_POSTGRES_URL")
-        .expect("NEON_POSTGRES_URL not found");
-
-    let firebase_auth_id = secrets
-        .get("FIREBASE_AUTH_PROJECT_ID")
-        .expect("Secret not found");
-
-    let storage_access_id = secrets
-        .get("STORAGE_ACCESS_ID")
-        .expect("STORAGE_ACCESS_ID not found");
-
-    let storage_access_token = secrets
-        .get("STORAGE_ACCESS_TOKEN")
-        .expect("STORAGE_ACCESS_TOKEN not found");
-
-    let app = setup_app(
-        &db_url,
-        &firebase_auth_id,
-        &storage_access_id,
-        &storage_access_token,
-    )
-    .await;
+    let config = app_config(|key| secrets.get(key));
+
+    let app = setup_app(config).await;
 
     let host = secrets.get("HOST").unwrap_or_else(|| "0.0.0.0".to_string());
     let port: u16 = secrets
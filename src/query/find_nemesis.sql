@@ -1,5 +1,5 @@
 WITH user_tags AS (
-    SELECT tag_name FROM UserTags WHERE user_id = $1
+    SELECT tag_name, stance FROM UserTags WHERE user_id = $1
 ),
 user_likes AS (
     SELECT target_user_id FROM UserLikes WHERE user_id = $1
@@ -7,108 +7,181 @@ user_likes AS (
 user_dislikes AS (
     SELECT target_user_id FROM UserDislikes WHERE user_id = $1
 ),
--- Get user's tags with embeddings
-user_tag_embeddings AS (
-    SELECT t.name, t.embedding
-    FROM Tags t
-    JOIN UserTags ut ON t.name = ut.tag_name
-    WHERE ut.user_id = $1 AND t.embedding IS NOT NULL
+-- Users hidden from the user: blocked by or blocking the user, suspended, or deleting their
+-- account
+user_blocks AS (
+    SELECT target_user_id AS blocked_user_id FROM UserBlocks WHERE user_id = $1
+    UNION
+    SELECT user_id AS blocked_user_id FROM UserBlocks WHERE target_user_id = $1
+    UNION
+    SELECT id AS blocked_user_id FROM Users u
+    WHERE NOT user_is_active(u)
 ),
--- Get potential nemesis users' tags with embeddings
-nemesis_tag_embeddings AS (
-    SELECT ut.user_id, t.name, t.embedding
-    FROM Tags t
-    JOIN UserTags ut ON t.name = ut.tag_name
-    WHERE ut.user_id != $1 
-        AND ut.user_id NOT IN (SELECT target_user_id FROM user_likes)
-        AND ut.user_id NOT IN (SELECT target_user_id FROM user_dislikes)
-        AND t.embedding IS NOT NULL
+-- Tags the user called out as reasons when disliking other users
+user_disliked_tags AS (
+    SELECT DISTINCT tag_name FROM UserDislikeTags WHERE user_id = $1
+),
+-- Opposition of every (user tag, potential nemesis tag) pair, see the user_tag_oppositions view
+tag_pair_oppositions AS (
+    SELECT
+        uto.other_user_id AS user_id,
+        uto.opposition,
+        -- Opposition within a category (vim vs emacs) and across categories (vim vs pizza)
+        -- is scaled separately
+        CASE
+            WHEN uto.same_category THEN $15::float8
+            ELSE $16::float8
+        END AS weight
+    FROM
+        user_tag_oppositions uto
+    WHERE uto.user_id = $1
+        AND uto.other_user_id NOT IN (SELECT target_user_id FROM user_likes)
+        AND uto.other_user_id NOT IN (SELECT target_user_id FROM user_dislikes)
+        AND uto.other_user_id NOT IN (SELECT blocked_user_id FROM user_blocks)
 ),
 -- Calculate tag embedding similarity scores for each potential nemesis
 tag_similarity_scores AS (
     SELECT 
-        nte.user_id,
-        -- For each user, calculate average similarity between their tags and opposite of user's tags
+        user_id,
+        -- Average scaled opposition between their tags and the user's tags, capped at 1 so
+        -- category weights above 1 keep the component in the 0-1 range of the others
         -- Higher score = more opposite tags (better nemesis match)
-        AVG(
-            CASE 
-                WHEN ute.embedding IS NOT NULL AND nte.embedding IS NOT NULL THEN
-                    -- Invert user tag embedding for opposite comparison
-                    -- Scale to 0-1 range where 1 = perfect opposite
-                    (1 - ((ute.embedding <=> nte.embedding) / 2))
-                ELSE 0.5
-            END
-        ) AS tag_embedding_score
+        LEAST(1, AVG(opposition * weight)) AS tag_embedding_score
     FROM 
-        nemesis_tag_embeddings nte
-    CROSS JOIN 
-        user_tag_embeddings ute
+        tag_pair_oppositions
     GROUP BY 
-        nte.user_id
+        user_id
 ),
--- Calculate combined score based on embedding similarity and tag overlap
-user_scores AS (
+-- Share of each potential nemesis' tags that the user has disliked on someone else
+-- 0 = none of their tags were disliked, 1 = every one of their tags was disliked
+-- Only candidates holding one of the user's disliked tags are counted, everyone else gets 0
+dislike_tag_penalties AS (
+    SELECT
+        ut.user_id,
+        COUNT(*)::float / (
+            SELECT COUNT(*)::float FROM UserTags ct WHERE ct.user_id = ut.user_id
+        ) AS dislike_tag_penalty
+    FROM
+        user_disliked_tags udt
+    JOIN
+        UserTags ut ON ut.tag_name = udt.tag_name
+    WHERE
+        ut.user_id != $1
+        AND ut.user_id NOT IN (SELECT target_user_id FROM user_likes)
+        AND ut.user_id NOT IN (SELECT target_user_id FROM user_dislikes)
+        AND ut.user_id NOT IN (SELECT blocked_user_id FROM user_blocks)
+    GROUP BY
+        ut.user_id
+),
+-- Calculate each score component on a 0-1 scale, higher = better nemesis match
+user_score_components AS (
     SELECT 
         u.id,
-        (
-            -- 1. User embedding similarity component (50%)
-            -- Higher score = better nemesis match (more opposite)
-            CASE
-                WHEN u.embedding IS NOT NULL THEN 
-                    -- Compare with negative embedding to find semantic opposites
-                    -- Scale to 0-1 range where 1 = perfect nemesis
-                    (1 - (u.embedding <=> $4::vector) / 2)
-                ELSE 0.5 -- Default middle value if no embedding
-            END * 0.5 -- 50% weight for user embedding
-            
-            +
-            
-            -- 2. Tag embedding similarity component (30%)
-            -- Use the tag similarity scores calculated above
-            COALESCE((
-                SELECT tag_embedding_score 
-                FROM tag_similarity_scores 
-                WHERE user_id = u.id
-            ), 0.5) * 0.3 -- 30% weight for tag embeddings
-            
-            +
-            
-            -- 3. Tag overlap component (20%)
-            -- Lower = more overlap, we want the opposite
-            -- Count percentage of non-matching tags
-            (1 - COALESCE((
-                SELECT COUNT(*)::float 
-                FROM UserTags ut
-                WHERE ut.user_id = u.id AND ut.tag_name IN (SELECT tag_name FROM user_tags)
-            ), 0) / 
-            NULLIF((
-                SELECT COUNT(*)::float 
-                FROM UserTags 
-                WHERE user_id = u.id
-            ), 1)) * 0.2 -- 20% weight for tag name overlap
-        ) AS nemesis_score
+        -- 1. User embedding component
+        CASE
+            WHEN u.embedding IS NOT NULL THEN 
+                -- Compare with negative embedding to find semantic opposites
+                -- Scale to 0-1 range where 1 = perfect nemesis
+                (1 - (u.embedding <=> $4::vector) / 2)
+            ELSE $11::float8 -- Fallback value if no embedding
+        END AS user_embedding_score,
+
+        -- 2. Tag embedding component
+        -- Use the tag similarity scores calculated above
+        COALESCE((
+            SELECT tag_embedding_score 
+            FROM tag_similarity_scores 
+            WHERE user_id = u.id
+        ), $11::float8) AS tag_embedding_score,
+
+        -- 3. Tag overlap component
+        -- Lower = more overlap, we want the opposite
+        -- Count percentage of non-matching tags, users without tags get the fallback value
+        -- Tags held with opposite stances don't match
+        COALESCE(1 - COALESCE((
+            SELECT COUNT(*)::float 
+            FROM UserTags ut
+            JOIN user_tags mt ON mt.tag_name = ut.tag_name AND (mt.stance > 0) = (ut.stance > 0)
+            WHERE ut.user_id = u.id
+        ), 0) / 
+        NULLIF((
+            SELECT COUNT(*)::float 
+            FROM UserTags 
+            WHERE user_id = u.id
+        ), 0), $11::float8) AS tag_overlap_score,
+
+        -- 4. Disliked tag penalty
+        -- Tags the user disliked on others push candidates holding them down the list,
+        -- diluted by how many other tags the candidate has
+        COALESCE((
+            SELECT dislike_tag_penalty
+            FROM dislike_tag_penalties
+            WHERE user_id = u.id
+        ), 0) AS dislike_tag_penalty
     FROM 
         Users u
     WHERE 
         u.id != $1
         AND u.id NOT IN (SELECT target_user_id FROM user_likes)
         AND u.id NOT IN (SELECT target_user_id FROM user_dislikes)
+        AND u.id NOT IN (SELECT blocked_user_id FROM user_blocks)
+),
+-- Blend the components into the final score with the weights chosen by the NemesisRanker
+user_scores AS (
+    SELECT 
+        usc.*,
+        (
+            usc.user_embedding_score * $7::float8
+            + usc.tag_embedding_score * $8::float8
+            + usc.tag_overlap_score * $9::float8
+            - usc.dislike_tag_penalty * $10::float8
+        ) AS nemesis_score
+    FROM 
+        user_score_components usc
 )
 
 SELECT 
-    u.id, u.username, u.display_name, u.avatar_url as "avatar_url: Url", u.bio, u.created_at, u.updated_at, u.embedding as "embedding: Vector", 
-    COALESCE(us.nemesis_score, 0.5) AS compatibility_score
-FROM 
-    Users u
-LEFT JOIN 
-    user_scores us ON u.id = us.id
-WHERE 
-    u.id != $1
-    AND u.id NOT IN (SELECT target_user_id FROM user_likes)
-    AND u.id NOT IN (SELECT target_user_id FROM user_dislikes)
+    c.id, c.username, c.display_name, c.avatar_url as "avatar_url: Url", c.bio, c.created_at, c.updated_at, c.embedding as "embedding: Vector", 
+    c.compatibility_score, c.user_embedding_score, c.tag_embedding_score, c.tag_overlap_score, c.dislike_tag_penalty
+FROM (
+    SELECT 
+        u.*,
+        COALESCE(us.nemesis_score, $11::float8) AS compatibility_score,
+        us.user_embedding_score,
+        us.tag_embedding_score,
+        us.tag_overlap_score,
+        us.dislike_tag_penalty
+    FROM 
+        Users u
+    LEFT JOIN 
+        user_scores us ON u.id = us.id
+    WHERE 
+        u.id != $1
+        AND u.id NOT IN (SELECT target_user_id FROM user_likes)
+        AND u.id NOT IN (SELECT target_user_id FROM user_dislikes)
+        AND u.id NOT IN (SELECT blocked_user_id FROM user_blocks)
+        -- Candidates must hold every required tag
+        AND (
+            SELECT COUNT(DISTINCT rt.tag_name)
+            FROM UserTags rt
+            WHERE rt.user_id = u.id AND rt.tag_name = ANY($12::text[])
+        ) = cardinality($12::text[])
+        -- And none of the excluded ones
+        AND NOT EXISTS (
+            SELECT 1
+            FROM UserTags xt
+            WHERE xt.user_id = u.id AND xt.tag_name = ANY($13::text[])
+        )
+) c
+WHERE
+    ($14::float8 IS NULL OR c.compatibility_score >= $14::float8)
+    -- Keyset pagination: only candidates after the (score, id) cursor of the previous page
+    AND (
+        $5::float8 IS NULL
+        OR (c.compatibility_score, c.id) < ($5::float8, $6::text)
+    )
 ORDER BY 
-    -- Order by nemesis score (highest first = most incompatible)
-    compatibility_score DESC
+    -- Order by nemesis score (highest first = most incompatible), id breaks ties
+    c.compatibility_score DESC, c.id DESC
 LIMIT $2
 OFFSET $3
-
@@ -1,10 +1,15 @@
 pub mod types;
+pub mod utils;
+#[cfg(feature = "dummy-auth")]
+use crate::auth::AuthUser;
 use crate::types::{ArchenemyState, Error};
 use axum::{
     body::Bytes,
-    extract::{Multipart, Path},
+    extract::{Multipart, Path, State},
     Extension, Json,
 };
+#[cfg(not(feature = "dummy-auth"))]
+use firebase_auth::FirebaseUser as AuthUser;
 use s3::{creds::Credentials, Bucket};
 use serde::Deserialize;
 use std::sync::Arc;
@@ -14,10 +19,17 @@ use uuid::Uuid;
 
 type BucketExtension = Arc<Bucket>;
 
-pub fn routes(access_id: &str, secret_access_key: &str) -> OpenApiRouter<ArchenemyState> {
+/// Public URL of the bucket, objects are served at `{PUBLIC_URL}/{name}`.
+pub const PUBLIC_URL: &str = "https://archenemy.nyc3.digitaloceanspaces.com";
+
+/// Name of the avatar users get by default, shared by everyone.
+pub const DEFAULT_AVATAR: &str = "default.jpeg";
+
+/// Connect to the bucket user uploads are stored in.
+pub fn bucket(access_id: &str, secret_access_key: &str) -> BucketExtension {
     let bucket = Bucket::new(
         "archenemy",
-        "https://archenemy.nyc3.digitaloceanspaces.com"
+        PUBLIC_URL
             .parse()
             .expect("Unable to create region for digital ocean"),
         Credentials::new(Some(access_id), Some(secret_access_key), None, None, None)
@@ -25,10 +37,14 @@ pub fn routes(access_id: &str, secret_access_key: &str) -> OpenApiRouter<Archene
     )
     .expect("Failed to create bucket");
 
+    Arc::new(*bucket)
+}
+
+pub fn routes(bucket: BucketExtension) -> OpenApiRouter<ArchenemyState> {
     OpenApiRouter::new()
         .routes(routes!(get_object))
         .routes(routes!(put_object))
-        .layer(Extension(Arc::new(*bucket)))
+        .layer(Extension(bucket))
 }
 
 /// Get an object from the bucket.
@@ -42,7 +58,7 @@ pub fn routes(access_id: &str, secret_access_key: &str) -> OpenApiRouter<Archene
 /// </div>
 ///
 /// Example:
-/// ```
+/// ```text
 /// GET /api/v1/storage/some_file
 /// ```
 #[utoipa::path(get, path="/{object}", tag=crate::tags::STORAGE, responses(
@@ -80,6 +96,10 @@ struct UploadForm {
 ///
 /// (I dont have an example because I don't event know how this looks like, it should be like a
 /// html form with input of type file)
+///
+/// <div class="info">
+/// The uploader is recorded, and their uploads are removed when their account is deleted.
+/// </div>
 #[utoipa::path(
     put,
     path = "/",
@@ -87,11 +107,17 @@ struct UploadForm {
     request_body(content = UploadForm, content_type = "multipart/form-data"),
     responses(
         (status = 200, description = "File uploaded successfully", body = types::PutResponse),
+        (status = 401, description = "Unauthorized"),
         (status = 500, description = "Internal server error", body = Error, example=json!(Error::Database { message: "S3 bucket error".to_string() })),
     ),
+    security(
+        ("firebase_auth_token" = [])
+    )
 )]
 async fn put_object(
+    State(state): State<ArchenemyState>,
     Extension(bucket): Extension<BucketExtension>,
+    user: AuthUser,
     mut multipart: Multipart,
 ) -> Result<Json<types::PutResponse>, Error> {
     let file_name = Uuid::new_v4();
@@ -110,6 +136,7 @@ async fn put_object(
                     .map_err(|e| Error::S3 {
                         msg: format!("{:?}", e),
                     })?;
+                utils::record_object(&state.pool, &file_name.to_string(), &user.user_id).await?;
                 break;
             }
         }
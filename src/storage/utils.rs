@@ -0,0 +1,68 @@
+use s3::Bucket;
+use sqlx::{self, postgres::PgPool};
+
+use crate::types::Error;
+
+type Result<T> = std::result::Result<T, Error>;
+
+// Record who uploaded an object, so it can be removed along with their account
+pub async fn record_object(pool: &PgPool, name: &str, user_id: &str) -> Result<()> {
+    sqlx::query!(
+        "INSERT INTO StorageObjects (name, user_id) VALUES ($1, $2)",
+        name,
+        user_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn get_user_objects(pool: &PgPool, user_id: &str) -> Result<Vec<String>> {
+    let names = sqlx::query_scalar!(
+        "SELECT name FROM StorageObjects WHERE user_id = $1 ORDER BY created_at",
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(names)
+}
+
+// The bucket object a user's avatar points at, when nobody is recorded as its uploader.
+// Uploads made before uploaders were recorded are only found this way, any other upload of
+// theirs is left in the bucket.
+pub async fn get_untracked_avatar_object(pool: &PgPool, user_id: &str) -> Result<Option<String>> {
+    let avatar_url = sqlx::query_scalar!("SELECT avatar_url FROM Users WHERE id = $1", user_id)
+        .fetch_optional(pool)
+        .await?;
+
+    let name = match avatar_url
+        .as_deref()
+        .and_then(|url| url.strip_prefix(super::PUBLIC_URL))
+        .and_then(|path| path.strip_prefix('/'))
+    {
+        Some(name) if !name.is_empty() && name != super::DEFAULT_AVATAR => name.to_string(),
+        _ => return Ok(None),
+    };
+
+    let tracked = sqlx::query_scalar!(
+        r#"SELECT EXISTS(SELECT 1 FROM StorageObjects WHERE name = $1) as "exists!""#,
+        name
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok((!tracked).then_some(name))
+}
+
+// Remove objects from the bucket, stopping at the first failure
+pub async fn delete_objects(bucket: &Bucket, names: &[String]) -> Result<()> {
+    for name in names {
+        bucket.delete_object(name).await.map_err(|e| Error::S3 {
+            msg: format!("{:?}", e),
+        })?;
+    }
+
+    Ok(())
+}
@@ -2,20 +2,31 @@ use axum::extract::FromRef;
 use axum::Json;
 use axum::{http::StatusCode, response::IntoResponse};
 use firebase_auth::FirebaseAuthState;
+use s3::Bucket;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Duration;
 use utoipa::ToSchema;
 
+use crate::embedding::SharedEmbeddingProvider;
+use crate::user::RankingConfig;
+
 #[derive(Clone, FromRef)]
 pub struct ArchenemyState {
     pub pool: PgPool,
     pub auth: FirebaseAuthState,
+    pub embedder: SharedEmbeddingProvider,
+    pub ranking: RankingConfig,
+    pub bucket: Arc<Bucket>,
+    pub account_deletion_grace: Duration,
 }
 
 #[derive(Serialize, Deserialize, Debug, ToSchema)]
 #[non_exhaustive]
 pub enum Error {
     Unauthenticated,
+    Forbidden { reason: String },
     NotFound { resource: String },
     Validation { field: String, message: String },
     Duplicate { resource: String },
@@ -31,6 +42,7 @@ impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Unauthenticated => write!(f, "Authentication failed or token missing"),
+            Self::Forbidden { reason } => write!(f, "Forbidden: {}", reason),
             Self::NotFound { resource } => write!(f, "Resource not found: {}", resource),
             Self::Validation { field, message } => {
                 write!(f, "Validation error in {}: {}", field, message)
@@ -59,6 +71,26 @@ impl From<sqlx::Error> for Error {
             sqlx::Error::RowNotFound => Error::NotFound {
                 resource: "database record".to_string(),
             },
+            // Raised by the database when creating a tag with a banned name
+            sqlx::Error::Database(ref db) if db.constraint() == Some("tags_name_not_banned") => {
+                Error::Validation {
+                    field: "tag_name".to_string(),
+                    message: "Tag name is not allowed".to_string(),
+                }
+            }
+            // Raised by the database when a name would be both a tag and an alias
+            sqlx::Error::Database(ref db) if db.constraint() == Some("tags_name_not_alias") => {
+                Error::Duplicate {
+                    resource: "Tag alias with the same name".to_string(),
+                }
+            }
+            sqlx::Error::Database(ref db)
+                if db.constraint() == Some("tag_aliases_alias_not_tag") =>
+            {
+                Error::Duplicate {
+                    resource: "Tag with the same name".to_string(),
+                }
+            }
             _ => Error::Database {
                 message: error.to_string(),
             },
@@ -70,6 +102,7 @@ impl IntoResponse for Error {
     fn into_response(self) -> axum::response::Response {
         let status_code = match &self {
             Self::Unauthenticated => StatusCode::UNAUTHORIZED,
+            Self::Forbidden { .. } => StatusCode::FORBIDDEN,
             Self::NotFound { .. } => StatusCode::NOT_FOUND,
             Self::Validation { .. } => StatusCode::BAD_REQUEST,
             Self::Duplicate { .. } => StatusCode::CONFLICT,
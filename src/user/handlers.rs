@@ -1,23 +1,33 @@
 #[cfg(feature = "dummy-auth")]
 use crate::auth::AuthUser;
 use axum::{
-    extract::{Path, Query, State},
-    http::StatusCode,
+    extract::{FromRef, FromRequestParts, Path, Query, State},
+    http::{header, request::Parts, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
 #[cfg(not(feature = "dummy-auth"))]
 use firebase_auth::FirebaseUser as AuthUser;
 use serde::{Deserialize, Serialize};
 use sqlx::types::chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::time::Duration;
 use utoipa::{IntoParams, ToSchema};
 
 use crate::types::{ArchenemyState, Error};
 use crate::user::types::{
-    CreateUserRequest, TagCount, UpdateUserRequest, User, UserDislike, UserDislikeTag, UserLike,
-    UserTag,
+    BannedTag, CreateUserRequest, Cursor, CursorKey, ModerationAction, NemesisFilter,
+    NemesisWeights, OpposingTagPair, ProfilePrivacy, PublicProfile, RankingConfig, RankingStrategy,
+    Report, ReportReason, ReportStatus, ScoreComponents, TagAlias, TagCategory, TagClassification,
+    TagCount, TagName, TagRelation, TagRelationKind, TrendingTag, TrendingWindow,
+    UpdateUserRequest, User, UserBlock, UserDislike, UserDislikeTag, UserLike, UserTag,
+    UserWarning, Username, Validate, Wrapped,
 };
 use crate::user::utils;
 
+/// Response header carrying the cursor of the next page, absent on the last page.
+pub const NEXT_CURSOR_HEADER: &str = "x-next-cursor";
+
 /// Parameters for pagination in API requests.
 ///
 /// Used to control the number of items returned and the starting position
@@ -27,22 +37,477 @@ pub struct PaginationParams {
     /// Maximum number of items to return. Defaults to endpoint-specific values if not provided.
     limit: Option<i64>,
     /// Number of items to skip before starting to return items. Defaults to 0 if not provided.
+    /// Deprecated in favor of `cursor` where supported, and ignored when `cursor` is given.
+    offset: Option<i64>,
+    /// Opaque cursor from the `x-next-cursor` header of the previous page. Omit for the first page.
+    cursor: Option<String>,
+}
+
+impl PaginationParams {
+    /// Decode the cursor, if any.
+    fn cursor<K: CursorKey>(&self) -> Result<Option<Cursor<K>>, Error> {
+        self.cursor.as_deref().map(Cursor::decode).transpose()
+    }
+
+    /// Offset to apply, cursors take precedence over offsets.
+    fn offset(&self) -> i64 {
+        match self.cursor {
+            Some(_) => 0,
+            None => self.offset.unwrap_or(0),
+        }
+    }
+}
+
+/// Parameters for debugging how nemesis scores are computed.
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct ExplainParams {
+    /// Include a `score_breakdown` for each candidate. Defaults to false.
+    explain: Option<bool>,
+}
+
+/// Parameters for narrowing down nemesis discovery.
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct NemesisFilterParams {
+    /// Comma-separated tags every candidate must hold, e.g. `vim,tabs`
+    require_tags: Option<String>,
+    /// Comma-separated tags no candidate may hold
+    exclude_tags: Option<String>,
+    /// Minimum compatibility score of the candidates
+    min_score: Option<f64>,
+}
+
+impl NemesisFilterParams {
+    /// Validate the parameters into a filter for the nemesis query.
+    fn filter(&self) -> Result<NemesisFilter, Error> {
+        let min_score = self.min_score;
+        if min_score.is_some_and(|score| !score.is_finite()) {
+            return Err(Error::Validation {
+                field: "min_score".to_string(),
+                message: "Minimum score must be a number".to_string(),
+            });
+        }
+
+        Ok(NemesisFilter {
+            require_tags: Self::tag_names("require_tags", self.require_tags.as_deref())?,
+            exclude_tags: Self::tag_names("exclude_tags", self.exclude_tags.as_deref())?,
+            min_score,
+        })
+    }
+
+    /// Split a comma-separated list of tags, validating each and dropping duplicates.
+    fn tag_names(field: &str, raw: Option<&str>) -> Result<Vec<TagName>, Error> {
+        let mut names: Vec<TagName> = Vec::new();
+
+        for name in raw.unwrap_or_default().split(',').map(TagName::normalize) {
+            if name.is_empty() || names.iter().any(|n| **n == name) {
+                continue;
+            }
+
+            let name = TagName::new(name).map_err(|e| match e {
+                Error::Validation { message, .. } => Error::Validation {
+                    field: field.to_string(),
+                    message,
+                },
+                e => e,
+            })?;
+            names.push(name);
+        }
+
+        Ok(names)
+    }
+}
+
+/// Request to alias a tag name to a canonical tag.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TagAliasRequest {
+    /// Name of the canonical tag the alias stands for
+    tag_name: String,
+}
+
+/// Request to ban a tag name.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BanTagRequest {
+    /// Why the tag name is banned, shown to admins only
+    #[serde(default)]
+    reason: String,
+}
+
+/// Request to rename a tag.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RenameTagRequest {
+    /// The new name of the tag
+    new_name: String,
+}
+
+/// Request to merge a tag into another.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MergeTagRequest {
+    /// Name of the tag that absorbs the merged one
+    into: String,
+}
+
+/// Request to find the tags most opposite to a set of tags.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TagSetNemesisRequest {
+    /// Tags to find the nemeses of, optionally with a stance, the current user's tags if not
+    /// provided
+    tags: Option<Vec<TagEntry>>,
+    /// Minimum nemesis score of the returned tags, from 0 to 1
+    min_score: Option<f64>,
+    /// Maximum number of tags to return, 10 if not provided and at most 50
+    limit: Option<i64>,
+}
+
+/// Parameters for listing related or opposed tags.
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct TagRelationParams {
+    /// Maximum number of tags to return, 10 if not provided and at most 50
+    limit: Option<i64>,
+}
+
+/// A pending deletion of the current user's account.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccountDeletion {
+    /// When the account will be deleted, unless the deletion is cancelled before then
+    delete_at: DateTime<Utc>,
+}
+
+/// Format of a personal data export.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    /// A single JSON document
+    #[default]
+    Json,
+    /// A zip archive holding the JSON document
+    Zip,
+}
+
+/// Parameters for exporting the current user's data.
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct ExportParams {
+    /// Format of the export, `json` if not provided
+    #[param(inline)]
+    format: Option<ExportFormat>,
+}
+
+/// Another user the current user liked, disliked or blocked.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ExportedUserRef {
+    user_id: String,
+    username: String,
+    /// When the current user liked, disliked or blocked them
+    created_at: DateTime<Utc>,
+}
+
+/// Another user the current user disliked, with the tags they gave as reasons.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ExportedDislike {
+    /// The disliked user flattened into this structure
+    #[serde(flatten)]
+    user: ExportedUserRef,
+    /// Tags given as reasons for the dislike
+    tags: Vec<String>,
+}
+
+/// Everything stored about the current user.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserExport {
+    /// When the export was generated
+    exported_at: DateTime<Utc>,
+    /// The user's own profile, as `GET /user/me` returns it
+    profile: User,
+    /// Who may see each optional field of the profile
+    privacy: ProfilePrivacy,
+    /// Tags on the profile, with their stances
+    tags: Vec<UserTag>,
+    /// Users the user liked
+    likes: Vec<ExportedUserRef>,
+    /// Users the user disliked
+    dislikes: Vec<ExportedDislike>,
+    /// Users the user blocked
+    blocks: Vec<ExportedUserRef>,
+    /// Names of the files the user uploaded, served under `/storage/{name}`
+    uploads: Vec<String>,
+}
+
+/// Whether a username is taken.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UsernameExists {
+    exists: bool,
+}
+
+/// Number of tag pairs found by a rebuild of the tag relations.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TagRelationsRebuild {
+    /// Pairs of tags held together
+    related: u64,
+    /// Pairs of tags held by users liking each other
+    opposed: u64,
+}
+
+/// Outcome of deleting the accounts past their grace period.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccountPurge {
+    /// Number of accounts deleted
+    deleted: usize,
+    /// IDs of the accounts that couldn't be deleted, left for the next run
+    failed: Vec<String>,
+}
+
+/// Parameters for listing tags.
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct TagListParams {
+    /// Only list tags in this category, including tags inheriting it from a parent
+    category: Option<String>,
+}
+
+/// Parameters for listing trending tags.
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct TrendingTagParams {
+    /// Window to compute trends over, `7d` if not provided
+    #[param(inline)]
+    window: Option<TrendingWindow>,
+    /// Maximum number of tags to return, 10 if not provided and at most 50
+    limit: Option<i64>,
+    /// Number of tags to skip, 0 if not provided
+    offset: Option<i64>,
+}
+
+/// Request to set the category and parent of a tag.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TagClassificationRequest {
+    /// Category of the tag, none to inherit the category of the parent
+    category: Option<String>,
+    /// Broader tag this tag is a specialization of, e.g. `vim` for `neovim`
+    parent_name: Option<String>,
+}
+
+/// Parameters for searching tags.
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct TagSearchParams {
+    /// Beginning of, or something close to, the tag name
+    q: String,
+    /// Maximum number of tags to return, 10 if not provided and at most 50
+    limit: Option<i64>,
+    /// Number of tags to skip, 0 if not provided
     offset: Option<i64>,
 }
 
+/// Per-request overrides of the nemesis ranking configuration, only available to admins.
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct RankingOverrideParams {
+    /// Ranking strategy to use instead of the configured one
+    strategy: Option<RankingStrategy>,
+    /// Weight of the user embedding opposition
+    user_embedding_weight: Option<f64>,
+    /// Weight of the tag embedding opposition
+    tag_embedding_weight: Option<f64>,
+    /// Weight of the share of tags not held by both users
+    tag_overlap_weight: Option<f64>,
+    /// Weight of the penalty for holding tags the user disliked
+    dislike_tag_penalty_weight: Option<f64>,
+    /// Scale of the opposition between tags from the same category
+    same_category_weight: Option<f64>,
+    /// Scale of the opposition between tags from different or unknown categories
+    cross_category_weight: Option<f64>,
+    /// Value used for score components that can't be computed
+    fallback_score: Option<f64>,
+}
+
+impl RankingOverrideParams {
+    /// Whether any override was given.
+    fn is_empty(&self) -> bool {
+        self.strategy.is_none()
+            && self.user_embedding_weight.is_none()
+            && self.tag_embedding_weight.is_none()
+            && self.tag_overlap_weight.is_none()
+            && self.dislike_tag_penalty_weight.is_none()
+            && self.same_category_weight.is_none()
+            && self.cross_category_weight.is_none()
+            && self.fallback_score.is_none()
+    }
+
+    /// Apply the overrides on top of the configured ranking.
+    fn apply(&self, config: &RankingConfig) -> Result<RankingConfig, Error> {
+        let weights = NemesisWeights {
+            user_embedding: self
+                .user_embedding_weight
+                .unwrap_or(config.weights.user_embedding),
+            tag_embedding: self
+                .tag_embedding_weight
+                .unwrap_or(config.weights.tag_embedding),
+            tag_overlap: self
+                .tag_overlap_weight
+                .unwrap_or(config.weights.tag_overlap),
+            dislike_tag_penalty: self
+                .dislike_tag_penalty_weight
+                .unwrap_or(config.weights.dislike_tag_penalty),
+            same_category: self
+                .same_category_weight
+                .unwrap_or(config.weights.same_category),
+            cross_category: self
+                .cross_category_weight
+                .unwrap_or(config.weights.cross_category),
+        };
+
+        let strategy = self.strategy.unwrap_or(config.strategy);
+
+        // Only the blended strategy uses the component weights, the others fix them
+        if strategy != RankingStrategy::Blended {
+            let fixed = [
+                ("user_embedding_weight", self.user_embedding_weight),
+                ("tag_embedding_weight", self.tag_embedding_weight),
+                ("tag_overlap_weight", self.tag_overlap_weight),
+            ];
+            if let Some((field, _)) = fixed.iter().find(|(_, weight)| weight.is_some()) {
+                return Err(Error::Validation {
+                    field: field.to_string(),
+                    message: "Component weights can only be overridden with the blended strategy"
+                        .to_string(),
+                });
+            }
+        }
+
+        let config = RankingConfig {
+            strategy,
+            weights,
+            fallback_score: self.fallback_score.unwrap_or(config.fallback_score),
+        };
+        config.validate()?;
+
+        Ok(config)
+    }
+}
+
+/// Maximum strength of a stance on a tag, in either direction.
+pub const MAX_TAG_STANCE: i16 = 3;
+
+/// Maximum number of tags set at once when replacing a user's tags, or given as a tag set.
+pub const MAX_USER_TAGS: usize = 100;
+
+/// Minimum number of users backing a pair of related or opposed tags, rarer pairs are noise.
+pub const MIN_TAG_RELATION_USERS: i64 = 2;
+
+/// Maximum number of tags returned by a single tag search or trending page.
+pub const MAX_TAG_SEARCH_LIMIT: i64 = 50;
+
+/// Maximum length of the free text of a report.
+pub const MAX_REPORT_DETAILS_LENGTH: usize = 1000;
+
+/// Maximum length of a suspension, in days.
+pub const MAX_SUSPEND_DAYS: i64 = 365;
+
+/// How long a deleted account can still be restored before it is gone for good, unless
+/// configured otherwise.
+pub const DEFAULT_ACCOUNT_DELETION_GRACE_PERIOD: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// An authenticated user holding the admin role.
+///
+/// Extracting it rejects unauthenticated requests like [`AuthUser`] does, and requests from
+/// users who aren't admins with a 403.
+#[derive(Debug, Clone)]
+pub struct AdminUser {
+    /// The ID of the authenticated admin.
+    pub user_id: String,
+}
+
+impl<S> FromRequestParts<S> for AdminUser
+where
+    S: Send + Sync,
+    AuthUser: FromRequestParts<S>,
+    ArchenemyState: FromRef<S>,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let user = AuthUser::from_request_parts(parts, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+
+        let state = ArchenemyState::from_ref(state);
+        match utils::is_admin(&state.pool, &user.user_id).await {
+            Ok(true) => Ok(AdminUser {
+                user_id: user.user_id,
+            }),
+            Ok(false) => Err(Error::Forbidden {
+                reason: "Admin role required".to_string(),
+            }
+            .into_response()),
+            Err(e) => Err(e.into_response()),
+        }
+    }
+}
+
+/// Reject users who are currently suspended.
+async fn ensure_not_suspended(state: &ArchenemyState, user_id: &str) -> Result<(), Error> {
+    match utils::get_active_suspension(&state.pool, user_id).await? {
+        Some(suspended_until) => Err(Error::Forbidden {
+            reason: format!("Account suspended until {}", suspended_until.to_rfc3339()),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Build the headers for a page of `limit` items, pointing at the next page if it may exist.
+fn page_headers<K: CursorKey>(len: usize, limit: i64, last: Option<Cursor<K>>) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+
+    if len as i64 >= limit {
+        if let Some(last) = last {
+            let value = HeaderValue::from_str(&last.encode())
+                .expect("Encoded cursors are always valid header values");
+            headers.insert(NEXT_CURSOR_HEADER, value);
+        }
+    }
+
+    headers
+}
+
 /// User information with their associated tags and optional compatibility score.
 ///
 /// Used in responses that need to provide both user data and their associated tags,
 /// particularly in nemesis matching endpoints.
 #[derive(Debug, Serialize, ToSchema)]
 pub struct UserWithTags {
-    /// The base user information flattened into this structure
+    /// The user's public profile flattened into this structure
     #[serde(flatten)]
-    user: User,
+    user: PublicProfile,
     /// Tags associated with this user
     tags: Vec<UserTag>,
     /// Optional compatibility score with the requesting user (higher indicates greater incompatibility)
     compatibility_score: Option<f64>,
+    /// How the compatibility score was computed, only present when explicitly requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    score_breakdown: Option<ScoreBreakdown>,
+}
+
+/// Breakdown of a nemesis compatibility score.
+///
+/// `compatibility_score` is the weighted sum of the components, minus the weighted
+/// `dislike_tag_penalty`.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ScoreBreakdown {
+    /// Strategy used to rank the candidates
+    strategy: RankingStrategy,
+    /// Value of each score component
+    components: ScoreComponents,
+    /// Weight applied to each component
+    weights: NemesisWeights,
+    /// The most strongly opposed pairs of tags between the two users
+    opposing_tags: Vec<OpposingTagPair>,
+}
+
+/// A user's profile together with their tags.
+///
+/// Used in responses for profile lookups.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserProfile {
+    /// The user's public profile flattened into this structure
+    #[serde(flatten)]
+    user: PublicProfile,
+    /// Tags associated with this user
+    tags: Vec<UserTag>,
 }
 
 /// User information with timestamp indicating when they were liked by the current user.
@@ -50,9 +515,9 @@ pub struct UserWithTags {
 /// Used in responses for liked user listings.
 #[derive(Debug, Serialize, ToSchema)]
 pub struct UserWithLikedAt {
-    /// The base user information flattened into this structure
+    /// The user's public profile flattened into this structure
     #[serde(flatten)]
-    user: User,
+    user: PublicProfile,
     /// Timestamp when the current user liked this user
     liked_at: DateTime<Utc>,
 }
@@ -62,20 +527,96 @@ pub struct UserWithLikedAt {
 /// Used in responses for disliked user listings.
 #[derive(Debug, Serialize, ToSchema)]
 pub struct UserWithDislikedAt {
-    /// The base user information flattened into this structure
+    /// The user's public profile flattened into this structure
     #[serde(flatten)]
-    user: User,
+    user: PublicProfile,
     /// Timestamp when the current user disliked this user
     disliked_at: DateTime<Utc>,
     /// Tags associated with the dislike, indicating reasons for disliking
     dislike_tags: Vec<UserDislikeTag>,
 }
 
+/// User information with timestamp indicating when they were blocked by the current user.
+///
+/// Used in responses for blocked user listings.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserWithBlockedAt {
+    /// The user's public profile flattened into this structure
+    #[serde(flatten)]
+    user: PublicProfile,
+    /// Timestamp when the current user blocked this user
+    blocked_at: DateTime<Utc>,
+}
+
+/// User information with their tags and the time a mutual match was formed.
+///
+/// Used in responses for matched user listings.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserWithMatchedAt {
+    /// The user and their tags flattened into this structure
+    #[serde(flatten)]
+    user: UserWithTags,
+    /// Timestamp when the like became mutual
+    matched_at: DateTime<Utc>,
+}
+
+/// Result of liking a user.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LikeResponse {
+    /// The like record flattened into this structure
+    #[serde(flatten)]
+    like: UserLike,
+    /// Whether this like completed a mutual match with the target user
+    matched: bool,
+}
+
 /// Request to add a single tag to a user.
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct AddTagRequest {
     /// Name of the tag to add
     tag_name: String,
+    /// How strongly the user is for (1 to 3) or against (-1 to -3) the tag, 1 if not provided
+    stance: Option<i16>,
+}
+
+impl AddTagRequest {
+    /// Validate the stance, if any.
+    fn stance(&self) -> Result<Option<i16>, Error> {
+        match self.stance {
+            Some(stance) if stance == 0 || stance.abs() > MAX_TAG_STANCE => {
+                Err(Error::Validation {
+                    field: "stance".to_string(),
+                    message: format!(
+                        "Stance must be between -{} and {}, and not 0",
+                        MAX_TAG_STANCE, MAX_TAG_STANCE
+                    ),
+                })
+            }
+            stance => Ok(stance),
+        }
+    }
+}
+
+/// A tag in the list replacing all tags of a user, either just its name or its name and the
+/// user's stance on it.
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum TagEntry {
+    Name(String),
+    WithStance(AddTagRequest),
+}
+
+impl TagEntry {
+    /// Validate the entry into a normalized tag name and its stance, if any.
+    fn validate(&self) -> Result<(String, Option<i16>), Error> {
+        let (name, stance) = match self {
+            TagEntry::Name(name) => (name, None),
+            TagEntry::WithStance(request) => (&request.tag_name, request.stance()?),
+        };
+        let name = TagName::new(TagName::normalize(name))?;
+
+        Ok((name.to_string(), stance))
+    }
 }
 
 /// Request to add multiple tags at once.
@@ -85,6 +626,42 @@ pub struct AddTagsRequest {
     tag_names: Vec<String>,
 }
 
+/// Request to report a user.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReportRequest {
+    /// Why the user is reported
+    reason: ReportReason,
+    /// Anything the moderators should know, at most 1000 characters
+    details: Option<String>,
+}
+
+/// Request to move a report through the moderation queue.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateReportRequest {
+    /// New status of the report
+    status: Option<ReportStatus>,
+    /// Notes for other moderators, replacing the previous notes
+    moderator_notes: Option<String>,
+}
+
+/// Request to act on a reported user.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReportActionRequest {
+    /// Action to take against the reported user
+    action: ModerationAction,
+    /// Message sent to the user with a warning or suspension, a generic one if not provided
+    message: Option<String>,
+    /// Length of a suspension in days, 7 if not provided
+    suspend_days: Option<i64>,
+}
+
+/// Parameters for filtering the moderation queue.
+#[derive(Debug, Deserialize, ToSchema, IntoParams)]
+pub struct ReportFilterParams {
+    /// Only list reports with this status
+    status: Option<ReportStatus>,
+}
+
 /// Tag representing a potential nemesis relationship with a score indicating strength.
 ///
 /// Higher nemesis scores indicate stronger semantic opposition between tags.
@@ -103,7 +680,7 @@ pub struct NemesisTag {
 /// user record in our database with default values including a randomly generated username.
 ///
 /// Example:
-/// ```
+/// ```text
 /// GET /user/me
 /// ```
 ///
@@ -147,7 +724,14 @@ pub async fn get_current_user(
                 bio: "".to_string(),
             };
 
-            utils::create_user(&state.pool, &create_request).await?
+            let user = utils::create_user(&state.pool, &create_request).await?;
+
+            #[cfg(feature = "dummy-auth")]
+            if user_id.starts_with(crate::auth::ADMIN_PREFIX) {
+                utils::grant_admin(&state.pool, &user_id).await?;
+            }
+
+            user
         }
     };
 
@@ -157,20 +741,31 @@ pub async fn get_current_user(
 /// Get a specific user by their ID.
 ///
 /// Retrieves public profile information for any user in the system.
-/// The requesting user must be authenticated but can view any other user's profile.
+/// The requesting user must be authenticated but can view any other user's profile,
+/// except for users they blocked or who blocked them.
 ///
 /// Example:
-/// ```
+/// ```text
 /// GET /user/abc123def456
+/// GET /user/@alicechen
 /// ```
+///
+/// <div class="info">
+/// Profile links can hold `@` and a username instead of the ID, so shared `@handles`
+/// resolve without another lookup. Only this route and `GET /user/{user_id}/tags` accept
+/// `@username`, every other route taking a user ID needs the `id` returned here.
+/// </div>
 #[utoipa::path(
     get,
     path="/user/{user_id}",
     tag=crate::tags::USER,
+    params(
+        ("user_id" = String, Path, description = "The ID of the user, or `@` and their username")
+    ),
     responses(
-        (status = 200, description = "Get user of id `user_id`", body = User),
+        (status = 200, description = "Get user of id `user_id`", body = PublicProfile),
         (status = 401, description = "Unauthorized"),
-        (status = 404, description = "User not found", body = Error, example = json!(Error::NotFound { resource: "User with ID abc123def456".to_string() })),
+        (status = 404, description = "User not found, or one of the users blocked the other", body = Error, example = json!(Error::NotFound { resource: "User with ID abc123def456".to_string() })),
         (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
     ),
     security(
@@ -180,15 +775,109 @@ pub async fn get_current_user(
 pub async fn get_user(
     State(state): State<ArchenemyState>,
     Path(user_id): Path<String>,
-    _user: AuthUser, // Ensure authenticated
-) -> Result<Json<User>, Error> {
-    let maybe_user = utils::get_user_by_id(&state.pool, &user_id).await?;
+    user: AuthUser,
+) -> Result<Json<PublicProfile>, Error> {
+    let maybe_user = utils::get_visible_user(&state.pool, &user.user_id, &user_id).await?;
 
-    let user = maybe_user.ok_or_else(|| Error::NotFound {
+    let target = maybe_user.ok_or_else(|| Error::NotFound {
         resource: format!("User with ID {}", user_id),
     })?;
 
-    Ok(Json(user))
+    let profile = utils::public_profile(&state.pool, &user.user_id, target).await?;
+    Ok(Json(profile))
+}
+
+/// Get a user's profile and tags by their username.
+///
+/// Resolves a shared `@handle` to the user behind it. Usernames are matched regardless of
+/// case, and a leading `@` is ignored.
+///
+/// Example:
+/// ```text
+/// GET /users/by-username/alicechen
+/// ```
+///
+/// <div class="info">
+/// Like looking users up by ID, users who blocked each other, or who are suspended, are
+/// not found.
+/// </div>
+#[utoipa::path(
+    get,
+    path="/users/by-username/{username}",
+    tag=crate::tags::USER,
+    params(
+        ("username" = String, Path, description = "The username of the user")
+    ),
+    responses(
+        (status = 200, description = "Get the profile and tags of the user with `username`", body = UserProfile),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "User not found, or one of the users blocked the other", body = Error, example = json!(Error::NotFound { resource: "User with username alicechen".to_string() })),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn get_user_by_username(
+    State(state): State<ArchenemyState>,
+    Path(username): Path<String>,
+    user: AuthUser,
+) -> Result<Json<UserProfile>, Error> {
+    let username = username.strip_prefix('@').unwrap_or(&username);
+
+    let maybe_user =
+        utils::get_visible_user_by_username(&state.pool, &user.user_id, username).await?;
+    let target = maybe_user.ok_or_else(|| Error::NotFound {
+        resource: format!("User with username {}", username),
+    })?;
+
+    let tags = utils::get_user_tags(&state.pool, &target.id).await?;
+    let profile = utils::public_profile(&state.pool, &user.user_id, target).await?;
+    Ok(Json(UserProfile {
+        user: profile,
+        tags,
+    }))
+}
+
+/// Check whether a username is taken.
+///
+/// Usernames are unique regardless of case, so `AliceChen` is taken once someone is
+/// `alicechen`. Use it to tell users whether a username is available before updating it.
+///
+/// Example:
+/// ```text
+/// GET /user/alicechen/exists
+/// ```
+///
+/// <div class="info">
+/// Users who blocked each other still see each other's usernames as taken.
+/// </div>
+#[utoipa::path(
+    get,
+    path="/user/{username}/exists",
+    tag=crate::tags::USER,
+    params(
+        ("username" = String, Path, description = "The username to look up")
+    ),
+    responses(
+        (status = 200, description = "Whether the username is taken", body = UsernameExists),
+        (status = 400, description = "The username isn't valid, so nobody can have it", body = Error, example = json!(Error::Validation { field: "username".to_string(), message: "Username is reserved".to_string() })),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn username_exists(
+    State(state): State<ArchenemyState>,
+    Path(username): Path<String>,
+    _user: AuthUser,
+) -> Result<Json<UsernameExists>, Error> {
+    let username = Username::new(username)?;
+
+    let exists = utils::username_exists(&state.pool, &username).await?;
+    Ok(Json(UsernameExists { exists }))
 }
 
 /// Update the current user's profile information.
@@ -197,7 +886,7 @@ pub async fn get_user(
 /// display name, avatar URL, and bio. Performs validation on inputs.
 ///
 /// Example:
-/// ```
+/// ```text
 /// PUT /user/me
 /// {
 ///   "username": "new_username",
@@ -208,8 +897,8 @@ pub async fn get_user(
 /// ```
 ///
 /// <div class="warning">
-/// Username cannot be empty if provided. All fields are optional - only provided
-/// fields will be updated.
+/// Usernames are 3 to 40 letters, digits, underscores or hyphens, and unique regardless of
+/// case. All fields are optional - only provided fields will be updated.
 /// </div>
 #[utoipa::path(
     put,
@@ -220,6 +909,7 @@ pub async fn get_user(
         (status = 400, description = "Validation error, in other words, bad request", body = Error, example = json!(Error::Validation { field: "username".to_string(), message: "Username cannot be empty".to_string() })),
         (status = 401, description = "Unauthorized"),
         (status = 404, description = "User not found", body = Error, example = json!(Error::NotFound { resource: "User with ID abc123def456".to_string() })),
+        (status = 409, description = "The username is taken", body = Error, example = json!(Error::Duplicate { resource: "Username alicechen".to_string() })),
         (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
     ),
     security(
@@ -235,622 +925,2763 @@ pub async fn update_current_user(
 
     // Validate input
     if let Some(ref username) = update.username {
-        if username.is_empty() {
-            return Err(Error::Validation {
-                field: "username".to_string(),
-                message: "Username cannot be empty".to_string(),
-            });
-        }
+        Username::new(username)?;
     }
 
-    let updated_user = utils::update_user(&state.pool, &user_id, &update).await?;
+    let updated_user =
+        utils::update_user(&state.pool, state.embedder.as_ref(), &user_id, &update).await?;
 
     Ok(Json(updated_user))
 }
 
-/// Get all tags in the system with their usage counts.
-///
-/// Returns a list of all tags that have been created by users in the system,
-/// along with a count of how many users have each tag. Tags are sorted by
-/// popularity (count) in descending order.
+/// Get who may see each optional field of the current user's profile.
 ///
 /// Example:
-/// ```
-/// GET /tags
+/// ```text
+/// GET /user/me/privacy
 /// ```
 #[utoipa::path(
     get,
-    path="/tags",
-    tag=crate::tags::TAGS,
+    path="/user/me/privacy",
+    tag=crate::tags::USER,
     responses(
-        (status = 200, description = "Return a list of tags with their corresponding counts", body = Vec<TagCount>),
+        (status = 200, description = "Get the privacy settings of the current user", body = ProfilePrivacy),
         (status = 401, description = "Unauthorized"),
+        (status = 404, description = "User not found", body = Error, example = json!(Error::NotFound { resource: "database record".to_string() })),
         (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
     ),
     security(
         ("firebase_auth_token" = [])
     )
 )]
-pub async fn get_all_tags(
+pub async fn get_current_user_privacy(
     State(state): State<ArchenemyState>,
-    _user: AuthUser, // Ensure authenticated
-) -> Result<Json<Vec<TagCount>>, Error> {
-    let tags = utils::get_all_tags(&state.pool).await?;
-    Ok(Json(tags))
+    user: AuthUser,
+) -> Result<Json<ProfilePrivacy>, Error> {
+    let privacy = utils::get_profile_privacy(&state.pool, &user.user_id).await?;
+    Ok(Json(privacy))
 }
 
-/// Find semantically opposite tags to a given tag.
+/// Set who may see each optional field of the current user's profile.
 ///
-/// This endpoint returns a list of tags that are considered "nemesis" tags
-/// to the specified tag. A nemesis tag is one that is semantically opposite
-/// or strongly conflicting with the given tag. Each returned tag includes a
-/// nemesis score indicating the strength of opposition.
+/// Each of `display_name`, `avatar_url` and `bio` is either `everyone` or `matches`. Fields
+/// only shown to matches are absent from the profile other users see until they match.
 ///
 /// Example:
-/// ```
-/// GET /tags/introvert/nemesis?limit=5
+/// ```text
+/// PUT /user/me/privacy
+/// {
+///   "display_name": "matches",
+///   "avatar_url": "everyone",
+///   "bio": "everyone"
+/// }
 /// ```
 ///
-/// <div class="info">
-/// Higher nemesis scores indicate stronger semantic opposition between tags.
-/// Results are sorted by nemesis score in descending order.
+/// <div class="warning">
+/// The settings replace the current ones, fields left out are visible to everyone.
 /// </div>
 #[utoipa::path(
-    get,
-    path="/tags/{tag_id}/nemesis",
-    tag=crate::tags::TAGS,
-    params(PaginationParams),
+    put,
+    path="/user/me/privacy",
+    tag=crate::tags::USER,
+    request_body = ProfilePrivacy,
     responses(
-        (status = 200, description = "Return a list of tags that is nemesis of the given tag id semantically, with a given score", body = Vec<NemesisTag>),
+        (status = 200, description = "Privacy settings successfully updated", body = ProfilePrivacy),
         (status = 401, description = "Unauthorized"),
+        (status = 404, description = "User not found", body = Error, example = json!(Error::NotFound { resource: "database record".to_string() })),
         (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
     ),
     security(
         ("firebase_auth_token" = [])
     )
-
 )]
-pub async fn get_nemesis_tags(
+pub async fn update_current_user_privacy(
     State(state): State<ArchenemyState>,
-    _user: AuthUser, // Ensure authenticated
-    Path(tag_name): Path<String>,
-    Query(pagination): Query<PaginationParams>,
-) -> Result<Json<Vec<NemesisTag>>, Error> {
-    let limit = pagination.limit.unwrap_or(10);
-    let nemesis_tags = utils::get_nemesis_tags(&state.pool, &tag_name, limit).await?;
-
-    // Convert to NemesisTag format
-    let formatted_tags = nemesis_tags
-        .into_iter()
-        .map(|(tag_name, nemesis_score)| NemesisTag {
-            tag_name,
-            nemesis_score,
-        })
-        .collect();
-
-    Ok(Json(formatted_tags))
+    user: AuthUser,
+    Json(privacy): Json<ProfilePrivacy>,
+) -> Result<Json<ProfilePrivacy>, Error> {
+    let privacy = utils::set_profile_privacy(&state.pool, &user.user_id, &privacy).await?;
+    Ok(Json(privacy))
 }
 
-/// Get all tags associated with a specific user.
+/// Delete the current user's account.
 ///
-/// Retrieves the list of tags that a user has added to their profile.
-/// These tags represent the user's interests, personality traits, or other characteristics.
+/// The account is hidden from other users right away and deleted for good after a grace
+/// period, which is 30 days unless configured otherwise. The deletion can be cancelled with
+/// `DELETE /user/me/deletion` until then.
+/// Deleting it removes the user's tags, likes, dislikes, matches and blocks, and the files
+/// they uploaded.
 ///
 /// Example:
+/// ```text
+/// DELETE /user/me
 /// ```
-/// GET /user/abc123def456/tags
-/// ```
+///
+/// <div class="warning">
+/// Once the grace period is over the account can't be restored. Deleting again while a
+/// deletion is pending doesn't move it back.
+/// </div>
 #[utoipa::path(
-    get,
-    path="/user/{user_id}/tags",
-    tags=[crate::tags::USER, crate::tags::TAGS],
+    delete,
+    path="/user/me",
+    tag=crate::tags::USER,
     responses(
-        (status = 200, description = "Get a list of tags thats attributed to user of id `user_id`", body = Vec<UserTag>),
+        (status = 202, description = "Account scheduled for deletion", body = AccountDeletion),
         (status = 401, description = "Unauthorized"),
-        (status = 404, description = "User not found", body = Error, example = json!(Error::NotFound { resource: "User with ID abc123def456".to_string() })),
+        (status = 404, description = "User not found", body = Error, example = json!(Error::NotFound { resource: "database record".to_string() })),
         (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
     ),
     security(
         ("firebase_auth_token" = [])
     )
-
 )]
-pub async fn get_user_tags(
+pub async fn delete_current_user(
     State(state): State<ArchenemyState>,
-    Path(user_id): Path<String>,
-    _user: AuthUser, // Ensure authenticated
-) -> Result<Json<Vec<UserTag>>, Error> {
-    // Verify user exists
-    let maybe_user = utils::get_user_by_id(&state.pool, &user_id).await?;
-    if maybe_user.is_none() {
-        return Err(Error::NotFound {
-            resource: format!("User with ID {}", user_id),
-        });
-    }
+    user: AuthUser,
+) -> Result<(StatusCode, Json<AccountDeletion>), Error> {
+    let delete_at =
+        utils::schedule_user_deletion(&state.pool, &user.user_id, state.account_deletion_grace)
+            .await?;
 
-    let tags = utils::get_user_tags(&state.pool, &user_id).await?;
-    Ok(Json(tags))
+    Ok((StatusCode::ACCEPTED, Json(AccountDeletion { delete_at })))
 }
 
-/// Get all tags associated with the current authenticated user.
-///
-/// Retrieves the list of tags that the current user has added to their profile.
-/// These tags represent the user's interests, personality traits, or other characteristics.
+/// Get the pending deletion of the current user's account.
 ///
 /// Example:
-/// ```
-/// GET /user/me/tags
+/// ```text
+/// GET /user/me/deletion
 /// ```
 #[utoipa::path(
     get,
-    path="/user/me/tags",
-    tags=[crate::tags::USER, crate::tags::TAGS],
+    path="/user/me/deletion",
+    tag=crate::tags::USER,
     responses(
-        (status = 200, description = "Get tags of the current user", body = Vec<UserTag>),
+        (status = 200, description = "Get when the account will be deleted", body = AccountDeletion),
         (status = 401, description = "Unauthorized"),
-        (status = 404, description = "User not found", body = Error, example = json!(Error::NotFound { resource: "User with ID abc123def456".to_string() })),
+        (status = 404, description = "No deletion pending", body = Error, example = json!(Error::NotFound { resource: "Pending account deletion".to_string() })),
         (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
     ),
     security(
         ("firebase_auth_token" = [])
     )
-
 )]
-pub async fn get_current_user_tags(
+pub async fn get_current_user_deletion(
     State(state): State<ArchenemyState>,
-    user: AuthUser, // Ensure authenticated
-) -> Result<Json<Vec<UserTag>>, Error> {
-    // Verify user exists
-    let maybe_user = utils::get_user_by_id(&state.pool, &user.user_id).await?;
-    if maybe_user.is_none() {
-        return Err(Error::NotFound {
-            resource: format!("User with ID {}", &user.user_id),
-        });
-    }
-
-    let tags = utils::get_user_tags(&state.pool, &user.user_id).await?;
-    Ok(Json(tags))
+    user: AuthUser,
+) -> Result<Json<AccountDeletion>, Error> {
+    let delete_at = utils::get_user_deletion(&state.pool, &user.user_id)
+        .await?
+        .ok_or_else(|| Error::NotFound {
+            resource: "Pending account deletion".to_string(),
+        })?;
+
+    Ok(Json(AccountDeletion { delete_at }))
 }
 
-/// Add a new tag to the current user's profile.
+/// Cancel the pending deletion of the current user's account.
 ///
-/// This endpoint allows users to add a single tag to their profile. Tags can represent
-/// interests, personality traits, or other characteristics. If the tag doesn't already
-/// exist in the system, it will be created.
+/// The account is visible to other users again, with everything it had. A deletion ordered by
+/// a moderator can't be cancelled.
 ///
 /// Example:
+/// ```text
+/// DELETE /user/me/deletion
 /// ```
-/// POST /user/me/tags
-/// {
-///   "tag_name": "introvert"
-/// }
-/// ```
-///
-/// <div class="warning">
-/// Tag name cannot be empty. Tags are case-sensitive.
-/// </div>
 #[utoipa::path(
-    post,
-    path="/user/me/tags",
-    tags=[crate::tags::USER, crate::tags::TAGS],
-    request_body = AddTagRequest,
+    delete,
+    path="/user/me/deletion",
+    tag=crate::tags::USER,
     responses(
-        (status = 200, description = "Add a tag to the current user", body = UserTag),
-        (status = 400, description = "Validation error, tag name cannot be empty", body = Error, example = json!(Error::Validation { field: "tag_name".to_string(), message: "Tag name cannot be empty".to_string() })),
+        (status = 204, description = "Deletion successfully cancelled"),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "The deletion was ordered by a moderator", body = Error, example = json!(Error::Forbidden { reason: "The account was deleted by a moderator".to_string() })),
+        (status = 404, description = "No deletion pending", body = Error, example = json!(Error::NotFound { resource: "Pending account deletion".to_string() })),
         (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
     ),
     security(
         ("firebase_auth_token" = [])
     )
 )]
-pub async fn add_current_user_tag(
+pub async fn cancel_current_user_deletion(
     State(state): State<ArchenemyState>,
     user: AuthUser,
-    Json(request): Json<AddTagRequest>,
-) -> Result<Json<UserTag>, Error> {
-    let user_id = user.user_id;
-
-    // Validate tag name
-    if request.tag_name.is_empty() {
-        return Err(Error::Validation {
-            field: "tag_name".to_string(),
-            message: "Tag name cannot be empty".to_string(),
+) -> Result<StatusCode, Error> {
+    if !utils::cancel_user_deletion(&state.pool, &user.user_id).await? {
+        return Err(Error::NotFound {
+            resource: "Pending account deletion".to_string(),
         });
     }
 
-    let tag = utils::add_user_tag(&state.pool, &user_id, &request.tag_name).await?;
-    Ok(Json(tag))
+    Ok(StatusCode::NO_CONTENT)
 }
 
-/// Remove one or more tags from the current user's profile.
+/// Export everything stored about the current user.
 ///
-/// This endpoint allows users to remove tags from their profile by providing
-/// a list of tag names to be removed.
+/// Returns the profile, privacy settings, tags, likes, dislikes with their tags, blocks and
+/// the names of uploaded files as one JSON document, or as a zip archive holding it with
+/// `format=zip`. Either is sent as a file download.
 ///
 /// Example:
-/// ```
-/// DELETE /user/me/tags
-/// ["introvert", "coffee-lover"]
+/// ```text
+/// GET /user/me/export
+/// GET /user/me/export?format=zip
 /// ```
 ///
 /// <div class="info">
-/// If any tags in the list are not found on the user's profile, they will be
-/// silently ignored. Returns a 204 No Content status on success.
+/// Other users only appear by ID and username. Unlike the lists of likes and dislikes,
+/// the export includes users the current user blocked or who blocked them.
 /// </div>
 #[utoipa::path(
-    delete,
-    path="/user/me/tags",
-    tags=[crate::tags::USER, crate::tags::TAGS],
-    request_body = Vec<String>,
+    get,
+    path="/user/me/export",
+    tag=crate::tags::USER,
+    params(ExportParams),
     responses(
-        (status = 204, description = "Tags successfully removed"),
+        (status = 200, description = "Export of the current user's data", content(
+            (UserExport = "application/json"),
+            (Vec<u8> = "application/zip")
+        )),
         (status = 401, description = "Unauthorized"),
+        (status = 404, description = "User not found", body = Error, example = json!(Error::NotFound { resource: "User with ID abc123def456".to_string() })),
         (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
     ),
     security(
         ("firebase_auth_token" = [])
     )
 )]
-pub async fn remove_current_user_tags(
+pub async fn export_current_user(
     State(state): State<ArchenemyState>,
     user: AuthUser,
-    Json(names): Json<Vec<String>>,
-) -> Result<StatusCode, Error> {
-    let user_id = user.user_id;
+    Query(params): Query<ExportParams>,
+) -> Result<Response, Error> {
+    let user_id = user.user_id.as_str();
+    let pool = &state.pool;
+
+    let profile = utils::get_user_by_id(pool, user_id)
+        .await?
+        .ok_or_else(|| Error::NotFound {
+            resource: format!("User with ID {}", user_id),
+        })?;
+    let privacy = utils::get_profile_privacy(pool, user_id).await?;
+    let tags = utils::get_user_tags(pool, user_id).await?;
+
+    let user_ref =
+        |(user_id, username, created_at): (String, String, DateTime<Utc>)| ExportedUserRef {
+            user_id,
+            username,
+            created_at,
+        };
+
+    let likes = utils::get_user_likes_for_export(pool, user_id)
+        .await?
+        .into_iter()
+        .map(user_ref)
+        .collect();
 
-    utils::remove_user_tag(&state.pool, &user_id, &names).await?;
-    Ok(StatusCode::NO_CONTENT)
+    let mut dislike_tags: HashMap<String, Vec<String>> = HashMap::new();
+    for tag in utils::get_user_dislike_tags_for_export(pool, user_id).await? {
+        dislike_tags
+            .entry(tag.target_user_id)
+            .or_default()
+            .push(tag.tag_name.to_string());
+    }
+    let dislikes = utils::get_user_dislikes_for_export(pool, user_id)
+        .await?
+        .into_iter()
+        .map(|disliked| ExportedDislike {
+            tags: dislike_tags.remove(&disliked.0).unwrap_or_default(),
+            user: user_ref(disliked),
+        })
+        .collect();
+
+    let blocks = utils::get_user_blocks_for_export(pool, user_id)
+        .await?
+        .into_iter()
+        .map(user_ref)
+        .collect();
+
+    let uploads = crate::storage::utils::get_user_objects(pool, user_id).await?;
+
+    let export = UserExport {
+        exported_at: Utc::now(),
+        profile,
+        privacy,
+        tags,
+        likes,
+        dislikes,
+        blocks,
+        uploads,
+    };
+
+    let response = match params.format.unwrap_or_default() {
+        ExportFormat::Json => (
+            [(
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"archenemy-export.json\"",
+            )],
+            Json(export),
+        )
+            .into_response(),
+        ExportFormat::Zip => (
+            [
+                (header::CONTENT_TYPE, "application/zip"),
+                (
+                    header::CONTENT_DISPOSITION,
+                    "attachment; filename=\"archenemy-export.zip\"",
+                ),
+            ],
+            zip_export(&export)?,
+        )
+            .into_response(),
+    };
+
+    Ok(response)
 }
 
-/// Get a list of potential nemeses for the current user.
+/// Pack an export into a zip archive holding `archenemy-export.json`.
+fn zip_export(export: &UserExport) -> Result<Vec<u8>, Error> {
+    use std::io::Write;
+    use zip::write::{SimpleFileOptions, ZipWriter};
+
+    let to_error = |e: &dyn std::fmt::Debug| Error::Unknown {
+        msg: format!("Failed to zip the export: {:?}", e),
+    };
+
+    let json = serde_json::to_vec_pretty(export).map_err(|e| to_error(&e))?;
+
+    let mut zip = ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    zip.start_file("archenemy-export.json", SimpleFileOptions::default())
+        .map_err(|e| to_error(&e))?;
+    zip.write_all(&json).map_err(|e| to_error(&e))?;
+    let archive = zip.finish().map_err(|e| to_error(&e))?;
+
+    Ok(archive.into_inner())
+}
+
+/// Get all tags in the system with their usage counts.
 ///
-/// This endpoint returns users who are potentially compatible as "nemeses" based on
-/// having opposing interests, personality traits, or characteristics. Results are sorted
-/// by a compatibility score, with higher scores indicating users who would make better nemeses.
+/// Returns a list of all tags that have been created by users in the system,
+/// along with a count of how many users have each tag. Tags are sorted by
+/// popularity (count) in descending order.
 ///
 /// Example:
-/// ```
-/// GET /nemeses?limit=10&offset=0
+/// ```text
+/// GET /tags
+/// GET /tags?category=programming
 /// ```
 ///
 /// <div class="info">
-/// The compatibility score is calculated based on how strongly a user's tags oppose the
-/// current user's tags. Users who have already been liked or disliked will not appear in results.
+/// Tags without a category of their own belong to the category of their closest
+/// categorized parent, e.g. `neovim` under `vim` is listed under `programming`.
 /// </div>
 #[utoipa::path(
     get,
-    path="/nemeses",
-    tag=crate::tags::USER,
-    params(PaginationParams),
+    path="/tags",
+    tag=crate::tags::TAGS,
+    params(TagListParams),
     responses(
-        (status = 200, description = "Get potential nemeses for the current user", body = Vec<UserWithTags>),
+        (status = 200, description = "Return a list of tags with their corresponding counts", body = Vec<TagCount>),
         (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Category not found", body = Error, example = json!(Error::NotFound { resource: "Tag category programming".to_string() })),
         (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
     ),
     security(
         ("firebase_auth_token" = [])
     )
 )]
-pub async fn get_potential_nemeses(
+pub async fn get_all_tags(
     State(state): State<ArchenemyState>,
-    user: AuthUser,
-    Query(pagination): Query<PaginationParams>,
-) -> Result<Json<Vec<UserWithTags>>, Error> {
-    let user_id = user.user_id;
-    let limit = pagination.limit.unwrap_or(10);
-    let offset = pagination.offset.unwrap_or(0);
-
-    let potential_nemeses =
-        utils::get_potential_nemeses(&state.pool, &user_id, limit, offset).await?;
-
-    // For each potential nemesis, get their tags
-    let mut result = Vec::new();
-    for (user, score) in potential_nemeses {
-        let tags = utils::get_user_tags(&state.pool, &user.id).await?;
+    _user: AuthUser, // Ensure authenticated
+    Query(params): Query<TagListParams>,
+) -> Result<Json<Vec<TagCount>>, Error> {
+    let category = params.category.as_deref().map(TagName::normalize);
 
-        result.push(UserWithTags {
-            user,
-            tags,
-            compatibility_score: Some(score),
-        });
+    if let Some(category) = &category {
+        if !utils::category_exists(&state.pool, category).await? {
+            return Err(Error::NotFound {
+                resource: format!("Tag category {}", category),
+            });
+        }
     }
 
-    Ok(Json(result))
+    let tags = utils::get_all_tags(&state.pool, category.as_deref()).await?;
+    Ok(Json(tags))
 }
 
-/// Like a user to indicate interest in establishing a nemesis relationship.
+/// Get the tags gaining the most users.
 ///
-/// This endpoint allows the current user to express interest in another user as a potential
-/// nemesis. If both users like each other, they become matched nemeses.
+/// Returns the tags added by the most users during the window, with how many users hold
+/// them now and their growth rate relative to the users holding them before the window.
 ///
 /// Example:
-/// ```
-/// POST /user/abc123def456/like
+/// ```text
+/// GET /tags/trending?window=24h
+/// GET /tags/trending?window=30d&limit=20&offset=20
 /// ```
 ///
-/// <div class="warning">
-/// Users cannot like themselves. If the target user has already liked the current user,
-/// this will create a match.
+/// <div class="info">
+/// `window` is one of `24h`, `7d` or `30d` and is rounded to the hour. Users who removed a
+/// tag again don't count as adopters. `limit` defaults to 10 and is capped at 50.
 /// </div>
 #[utoipa::path(
-    post,
-    path="/user/{target_user_id}/like",
-    tag=crate::tags::USER,
-    params(
-        ("target_user_id" = String, Path, description = "ID of the user to like")
-    ),
+    get,
+    path="/tags/trending",
+    tag=crate::tags::TAGS,
+    params(TrendingTagParams),
     responses(
-        (status = 200, description = "User successfully liked", body = UserLike),
-        (status = 400, description = "Cannot like yourself", body = Error, example = json!(Error::Validation { field: "target_user_id".to_string(), message: "Cannot like yourself".to_string() })),
+        (status = 200, description = "Return the trending tags", body = Vec<TrendingTag>),
+        (status = 400, description = "Invalid window"),
         (status = 401, description = "Unauthorized"),
-        (status = 404, description = "Target user not found", body = Error, example = json!(Error::NotFound { resource: "User with ID abc123def456".to_string() })),
         (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
     ),
     security(
         ("firebase_auth_token" = [])
     )
 )]
-pub async fn like_user(
+pub async fn get_trending_tags(
     State(state): State<ArchenemyState>,
-    user: AuthUser,
-    Path(target_user_id): Path<String>,
-) -> Result<Json<UserLike>, Error> {
-    let user_id = user.user_id;
-
-    // Verify target user exists
-    let maybe_target = utils::get_user_by_id(&state.pool, &target_user_id).await?;
-    if maybe_target.is_none() {
-        return Err(Error::NotFound {
-            resource: format!("User with ID {}", target_user_id),
-        });
-    }
-
-    // Prevent liking oneself
-    if user_id == target_user_id {
-        return Err(Error::Validation {
-            field: "target_user_id".to_string(),
-            message: "Cannot like yourself".to_string(),
-        });
-    }
+    _user: AuthUser, // Ensure authenticated
+    Query(params): Query<TrendingTagParams>,
+) -> Result<Json<Vec<TrendingTag>>, Error> {
+    let window = params.window.unwrap_or_default();
+    let limit = params.limit.unwrap_or(10).clamp(1, MAX_TAG_SEARCH_LIMIT);
+    let offset = params.offset.unwrap_or(0).max(0);
 
-    let like = utils::add_like(&state.pool, &user_id, &target_user_id).await?;
-    Ok(Json(like))
+    let tags = utils::get_trending_tags(&state.pool, window.hours(), limit, offset).await?;
+    Ok(Json(tags))
 }
 
-/// Dislike a user to indicate lack of interest in establishing a nemesis relationship.
+/// Get all tag categories.
 ///
-/// This endpoint allows the current user to express that they are not interested in
-/// another user as a potential nemesis. Disliked users will not appear in future
-/// potential nemesis suggestions.
+/// Returns every category tags can be filed under, with the number of tags in each.
+/// Categories are sorted by name.
 ///
 /// Example:
+/// ```text
+/// GET /tags/categories
 /// ```
+#[utoipa::path(
+    get,
+    path="/tags/categories",
+    tag=crate::tags::TAGS,
+    responses(
+        (status = 200, description = "Return a list of tag categories", body = Vec<TagCategory>),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn get_tag_categories(
+    State(state): State<ArchenemyState>,
+    _user: AuthUser, // Ensure authenticated
+) -> Result<Json<Vec<TagCategory>>, Error> {
+    let categories = utils::get_tag_categories(&state.pool).await?;
+    Ok(Json(categories))
+}
+
+/// Search tags by name, for autocompletion.
+///
+/// Returns tags starting with `q` first, then tags with a similar spelling, so typos still
+/// find the intended tag. Within each group tags are sorted by popularity.
+///
+/// Example:
+/// ```text
+/// GET /tags/search?q=vi&limit=10
+/// GET /tags/search?q=vi&limit=10&offset=10
+/// ```
+///
+/// <div class="info">
+/// Matching ignores case, and tags with an alias starting with `q` are found too.
+/// `limit` defaults to 10 and is capped at 50.
+/// </div>
+#[utoipa::path(
+    get,
+    path="/tags/search",
+    tag=crate::tags::TAGS,
+    params(TagSearchParams),
+    responses(
+        (status = 200, description = "Return the matching tags with their corresponding counts", body = Vec<TagCount>),
+        (status = 400, description = "Invalid search query", body = Error, example = json!(Error::Validation { field: "q".to_string(), message: "Tag name cannot be empty".to_string() })),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn search_tags(
+    State(state): State<ArchenemyState>,
+    _user: AuthUser, // Ensure authenticated
+    Query(params): Query<TagSearchParams>,
+) -> Result<Json<Vec<TagCount>>, Error> {
+    // Anything that can't be part of a tag name can't match one either
+    let query = TagName::new(TagName::normalize(&params.q)).map_err(|e| match e {
+        Error::Validation { message, .. } => Error::Validation {
+            field: "q".to_string(),
+            message,
+        },
+        e => e,
+    })?;
+    let limit = params.limit.unwrap_or(10).clamp(1, MAX_TAG_SEARCH_LIMIT);
+    let offset = params.offset.unwrap_or(0).max(0);
+
+    let tags = utils::search_tags(&state.pool, &query, limit, offset).await?;
+    Ok(Json(tags))
+}
+
+/// Find semantically opposite tags to a given tag.
+///
+/// This endpoint returns a list of tags that are considered "nemesis" tags
+/// to the specified tag. A nemesis tag is one that is semantically opposite
+/// or strongly conflicting with the given tag. Each returned tag includes a
+/// nemesis score indicating the strength of opposition.
+///
+/// Example:
+/// ```text
+/// GET /tags/introvert/nemesis?limit=5
+/// ```
+///
+/// <div class="info">
+/// Higher nemesis scores indicate stronger semantic opposition between tags.
+/// Results are sorted by nemesis score in descending order.
+/// </div>
+#[utoipa::path(
+    get,
+    path="/tags/{tag_id}/nemesis",
+    tag=crate::tags::TAGS,
+    params(PaginationParams),
+    responses(
+        (status = 200, description = "Return a list of tags that is nemesis of the given tag id semantically, with a given score", body = Vec<NemesisTag>),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+
+)]
+pub async fn get_nemesis_tags(
+    State(state): State<ArchenemyState>,
+    _user: AuthUser, // Ensure authenticated
+    Path(tag_name): Path<String>,
+    Query(pagination): Query<PaginationParams>,
+) -> Result<Json<Vec<NemesisTag>>, Error> {
+    let limit = pagination.limit.unwrap_or(10);
+    let nemesis_tags =
+        utils::get_nemesis_tags(&state.pool, state.embedder.as_ref(), &tag_name, limit).await?;
+
+    // Convert to NemesisTag format
+    let formatted_tags = nemesis_tags
+        .into_iter()
+        .map(|(tag_name, nemesis_score)| NemesisTag {
+            tag_name,
+            nemesis_score,
+        })
+        .collect();
+
+    Ok(Json(formatted_tags))
+}
+
+/// Find the tags most opposite to a set of tags.
+///
+/// Combines the given tags, or the current user's tags if none are given, into one centroid
+/// and returns the tags pointing the other way. Use it to suggest what a nemesis of someone
+/// with these tags would be into.
+///
+/// Example:
+/// ```text
+/// POST /tags/nemesis
+/// {
+///   "tags": ["vim", "tabs", { "tag_name": "pineapple-pizza", "stance": -3 }],
+///   "min_score": 0.6,
+///   "limit": 5
+/// }
+///
+/// POST /tags/nemesis
+/// {}
+/// ```
+///
+/// <div class="info">
+/// Nemesis scores go from 0 (same direction as the tags) to 1 (perfect opposite), results
+/// are sorted by nemesis score in descending order. Tags with a negative stance count as
+/// their opposite. The given tags and tags the current user already has are never returned.
+/// At most 100 tags can be given. `limit` defaults to 10 and is capped at 50.
+/// </div>
+#[utoipa::path(
+    post,
+    path="/tags/nemesis",
+    tag=crate::tags::TAGS,
+    request_body = TagSetNemesisRequest,
+    responses(
+        (status = 200, description = "Return the tags most opposite to the set, with their nemesis score", body = Vec<NemesisTag>),
+        (status = 400, description = "Invalid tag, stance or score, no tags to start from or too many, or stances that cancel each other out", body = Error, example = json!(Error::Validation { field: "tags".to_string(), message: "At least one tag is required".to_string() })),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn get_tag_set_nemesis_tags(
+    State(state): State<ArchenemyState>,
+    user: AuthUser,
+    Json(request): Json<TagSetNemesisRequest>,
+) -> Result<Json<Vec<NemesisTag>>, Error> {
+    if request
+        .tags
+        .as_ref()
+        .is_some_and(|entries| entries.len() > MAX_USER_TAGS)
+    {
+        return Err(Error::Validation {
+            field: "tags".to_string(),
+            message: format!("At most {} tags can be given", MAX_USER_TAGS),
+        });
+    }
+
+    let tags = request
+        .tags
+        .as_ref()
+        .map(|entries| {
+            entries
+                .iter()
+                .map(TagEntry::validate)
+                .collect::<Result<Vec<_>, Error>>()
+        })
+        .transpose()?;
+
+    if request
+        .min_score
+        .is_some_and(|score| !(0.0..=1.0).contains(&score))
+    {
+        return Err(Error::Validation {
+            field: "min_score".to_string(),
+            message: "Minimum score must be between 0 and 1".to_string(),
+        });
+    }
+    let limit = request.limit.unwrap_or(10).clamp(1, MAX_TAG_SEARCH_LIMIT);
+
+    let nemesis_tags = utils::get_nemesis_tags_for_set(
+        &state.pool,
+        state.embedder.as_ref(),
+        &user.user_id,
+        tags.as_deref(),
+        request.min_score,
+        limit,
+    )
+    .await?;
+
+    let formatted_tags = nemesis_tags
+        .into_iter()
+        .map(|(tag_name, nemesis_score)| NemesisTag {
+            tag_name,
+            nemesis_score,
+        })
+        .collect();
+
+    Ok(Json(formatted_tags))
+}
+
+/// Get the tags most commonly held together with a tag.
+///
+/// Tags are scored by how much more often than chance users hold both, as lift and pointwise
+/// mutual information (PMI). Results are sorted by PMI in descending order.
+///
+/// Example:
+/// ```text
+/// GET /tags/vim/related?limit=5
+/// ```
+///
+/// <div class="info">
+/// Relations are rebuilt periodically, see `computed_at`, so recent tag changes may not show
+/// up yet. Pairs held by fewer than 2 users are left out. Users against a tag (negative
+/// stance) don't count as holding it.
+/// </div>
+#[utoipa::path(
+    get,
+    path="/tags/{tag_name}/related",
+    tag=crate::tags::TAGS,
+    params(
+        ("tag_name" = String, Path, description = "The tag to find related tags of"),
+        TagRelationParams
+    ),
+    responses(
+        (status = 200, description = "Return the tags most related to the tag", body = Vec<TagRelation>),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Tag not found", body = Error, example = json!(Error::NotFound { resource: "Tag vim".to_string() })),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn get_related_tags(
+    State(state): State<ArchenemyState>,
+    _user: AuthUser, // Ensure authenticated
+    Path(tag_name): Path<String>,
+    Query(params): Query<TagRelationParams>,
+) -> Result<Json<Vec<TagRelation>>, Error> {
+    tag_relations(&state, &tag_name, TagRelationKind::Related, &params).await
+}
+
+/// Get the tags most often held by users who liked holders of a tag.
+///
+/// Where `GET /tags/{tag_name}/nemesis` goes by what tags mean, this goes by what users do:
+/// tags held by the users who want holders of the tag as their nemesis. Tags are scored by
+/// lift and pointwise mutual information (PMI) against how common they are overall. Results
+/// are sorted by PMI in descending order.
+///
+/// Example:
+/// ```text
+/// GET /tags/vim/opposed?limit=5
+/// ```
+///
+/// <div class="info">
+/// Relations are rebuilt periodically, see `computed_at`, so recent likes may not show up
+/// yet. Pairs backed by fewer than 2 users are left out. Users against a tag (negative
+/// stance) don't count as holding it.
+/// </div>
+#[utoipa::path(
+    get,
+    path="/tags/{tag_name}/opposed",
+    tag=crate::tags::TAGS,
+    params(
+        ("tag_name" = String, Path, description = "The tag to find opposed tags of"),
+        TagRelationParams
+    ),
+    responses(
+        (status = 200, description = "Return the tags most opposed to the tag", body = Vec<TagRelation>),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Tag not found", body = Error, example = json!(Error::NotFound { resource: "Tag vim".to_string() })),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn get_opposed_tags(
+    State(state): State<ArchenemyState>,
+    _user: AuthUser, // Ensure authenticated
+    Path(tag_name): Path<String>,
+    Query(params): Query<TagRelationParams>,
+) -> Result<Json<Vec<TagRelation>>, Error> {
+    tag_relations(&state, &tag_name, TagRelationKind::Opposed, &params).await
+}
+
+/// Look up the relations of one kind of an existing tag.
+async fn tag_relations(
+    state: &ArchenemyState,
+    tag_name: &str,
+    kind: TagRelationKind,
+    params: &TagRelationParams,
+) -> Result<Json<Vec<TagRelation>>, Error> {
+    let tag_name = utils::canonical_tag_name(&state.pool, tag_name).await?;
+    if !utils::tag_exists(&state.pool, &tag_name).await? {
+        return Err(Error::NotFound {
+            resource: format!("Tag {}", tag_name),
+        });
+    }
+    let limit = params.limit.unwrap_or(10).clamp(1, MAX_TAG_SEARCH_LIMIT);
+
+    let relations = utils::get_tag_relations(&state.pool, &tag_name, kind, limit).await?;
+    Ok(Json(relations))
+}
+
+/// Get all tags associated with a specific user.
+///
+/// Retrieves the list of tags that a user has added to their profile.
+/// These tags represent the user's interests, personality traits, or other characteristics.
+///
+/// Example:
+/// ```text
+/// GET /user/abc123def456/tags
+/// GET /user/@alicechen/tags
+/// ```
+#[utoipa::path(
+    get,
+    path="/user/{user_id}/tags",
+    tags=[crate::tags::USER, crate::tags::TAGS],
+    params(
+        ("user_id" = String, Path, description = "The ID of the user, or `@` and their username")
+    ),
+    responses(
+        (status = 200, description = "Get a list of tags thats attributed to user of id `user_id`", body = Vec<UserTag>),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "User not found, or one of the users blocked the other", body = Error, example = json!(Error::NotFound { resource: "User with ID abc123def456".to_string() })),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+
+)]
+pub async fn get_user_tags(
+    State(state): State<ArchenemyState>,
+    Path(user_id): Path<String>,
+    user: AuthUser,
+) -> Result<Json<Vec<UserTag>>, Error> {
+    // Verify user exists and the two users haven't blocked each other
+    let maybe_user = utils::get_visible_user(&state.pool, &user.user_id, &user_id).await?;
+    let Some(target) = maybe_user else {
+        return Err(Error::NotFound {
+            resource: format!("User with ID {}", user_id),
+        });
+    };
+
+    let tags = utils::get_user_tags(&state.pool, &target.id).await?;
+    Ok(Json(tags))
+}
+
+/// Get all tags associated with the current authenticated user.
+///
+/// Retrieves the list of tags that the current user has added to their profile.
+/// These tags represent the user's interests, personality traits, or other characteristics.
+///
+/// Example:
+/// ```text
+/// GET /user/me/tags
+/// ```
+#[utoipa::path(
+    get,
+    path="/user/me/tags",
+    tags=[crate::tags::USER, crate::tags::TAGS],
+    responses(
+        (status = 200, description = "Get tags of the current user", body = Vec<UserTag>),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "User not found", body = Error, example = json!(Error::NotFound { resource: "User with ID abc123def456".to_string() })),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+
+)]
+pub async fn get_current_user_tags(
+    State(state): State<ArchenemyState>,
+    user: AuthUser, // Ensure authenticated
+) -> Result<Json<Vec<UserTag>>, Error> {
+    // Verify user exists
+    let maybe_user = utils::get_user_by_id(&state.pool, &user.user_id).await?;
+    if maybe_user.is_none() {
+        return Err(Error::NotFound {
+            resource: format!("User with ID {}", &user.user_id),
+        });
+    }
+
+    let tags = utils::get_user_tags(&state.pool, &user.user_id).await?;
+    Ok(Json(tags))
+}
+
+/// Add a new tag to the current user's profile.
+///
+/// This endpoint allows users to add a single tag to their profile. Tags can represent
+/// interests, personality traits, or other characteristics. If the tag doesn't already
+/// exist in the system, it will be created.
+///
+/// Example:
+/// ```text
+/// POST /user/me/tags
+/// {
+///   "tag_name": "introvert"
+/// }
+///
+/// POST /user/me/tags
+/// {
+///   "tag_name": "pineapple-pizza",
+///   "stance": -3
+/// }
+/// ```
+///
+/// <div class="info">
+/// `stance` says whether the user is for (1 to 3) or against (-1 to -3) the tag and how
+/// strongly, it defaults to 1. Users with opposite stances on the same tag make good
+/// nemeses. Adding a tag the user already has changes its stance.
+/// </div>
+///
+/// <div class="warning">
+/// Tag name cannot be empty. Tags are case-insensitive: `Rust` and `rust` are the same tag,
+/// stored in lowercase.
+/// </div>
+#[utoipa::path(
+    post,
+    path="/user/me/tags",
+    tags=[crate::tags::USER, crate::tags::TAGS],
+    request_body = AddTagRequest,
+    responses(
+        (status = 200, description = "Add a tag to the current user", body = UserTag),
+        (status = 400, description = "Validation error, tag name cannot be empty or stance out of range", body = Error, example = json!(Error::Validation { field: "tag_name".to_string(), message: "Tag name cannot be empty".to_string() })),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn add_current_user_tag(
+    State(state): State<ArchenemyState>,
+    user: AuthUser,
+    Json(request): Json<AddTagRequest>,
+) -> Result<Json<UserTag>, Error> {
+    let user_id = user.user_id;
+
+    // Validate tag name
+    TagName::new(TagName::normalize(&request.tag_name))?;
+    let stance = request.stance()?;
+
+    let tag = utils::add_user_tag(
+        &state.pool,
+        state.embedder.as_ref(),
+        &user_id,
+        &request.tag_name,
+        stance,
+    )
+    .await?;
+    Ok(Json(tag))
+}
+
+/// Replace all tags on the current user's profile.
+///
+/// Takes the complete list of tags the user should have. Tags missing from the list are
+/// removed, new ones are added and created if needed, all at once.
+///
+/// Example:
+/// ```text
+/// PUT /user/me/tags
+/// ["introvert", "coffee-lover", { "tag_name": "pineapple-pizza", "stance": -3 }]
+/// ```
+///
+/// <div class="info">
+/// Tag names are case-folded and aliases resolve to their canonical tag, duplicates are
+/// ignored. Tags given by name only keep the stance they have, new ones get a stance of 1.
+/// An empty list removes every tag, and at most 100 tags can be given. Either every change is
+/// applied or none is.
+/// </div>
+#[utoipa::path(
+    put,
+    path="/user/me/tags",
+    tags=[crate::tags::USER, crate::tags::TAGS],
+    request_body = Vec<TagEntry>,
+    responses(
+        (status = 200, description = "The current user's tags after the replacement", body = Vec<UserTag>),
+        (status = 400, description = "Invalid tag name or stance, or too many tags", body = Error, example = json!(Error::Validation { field: "tag_name".to_string(), message: "Tag name cannot be empty".to_string() })),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Current user not found", body = Error, example = json!(Error::NotFound { resource: "User with ID abc123def456".to_string() })),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn replace_current_user_tags(
+    State(state): State<ArchenemyState>,
+    user: AuthUser,
+    Json(entries): Json<Vec<TagEntry>>,
+) -> Result<Json<Vec<UserTag>>, Error> {
+    let user_id = user.user_id;
+
+    if entries.len() > MAX_USER_TAGS {
+        return Err(Error::Validation {
+            field: "tags".to_string(),
+            message: format!("At most {} tags can be set", MAX_USER_TAGS),
+        });
+    }
+
+    // Validate every entry before changing anything
+    let tags = entries
+        .iter()
+        .map(TagEntry::validate)
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    // Verify user exists
+    if utils::get_user_by_id(&state.pool, &user_id)
+        .await?
+        .is_none()
+    {
+        return Err(Error::NotFound {
+            resource: format!("User with ID {}", user_id),
+        });
+    }
+
+    let tags = utils::set_user_tags(&state.pool, state.embedder.as_ref(), &user_id, &tags).await?;
+    Ok(Json(tags))
+}
+
+/// Remove one or more tags from the current user's profile.
+///
+/// This endpoint allows users to remove tags from their profile by providing
+/// a list of tag names to be removed.
+///
+/// Example:
+/// ```text
+/// DELETE /user/me/tags
+/// ["introvert", "coffee-lover"]
+/// ```
+///
+/// <div class="info">
+/// If any tags in the list are not found on the user's profile, they will be
+/// silently ignored. Returns a 204 No Content status on success.
+/// </div>
+#[utoipa::path(
+    delete,
+    path="/user/me/tags",
+    tags=[crate::tags::USER, crate::tags::TAGS],
+    request_body = Vec<String>,
+    responses(
+        (status = 204, description = "Tags successfully removed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn remove_current_user_tags(
+    State(state): State<ArchenemyState>,
+    user: AuthUser,
+    Json(names): Json<Vec<String>>,
+) -> Result<StatusCode, Error> {
+    let user_id = user.user_id;
+
+    utils::remove_user_tag(&state.pool, state.embedder.as_ref(), &user_id, &names).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Get a list of potential nemeses for the current user.
+///
+/// This endpoint returns users who are potentially compatible as "nemeses" based on
+/// having opposing interests, personality traits, or characteristics. Results are sorted
+/// by a compatibility score, with higher scores indicating users who would make better nemeses.
+///
+/// Example:
+/// ```text
+/// GET /nemeses?limit=10
+/// GET /nemeses?limit=10&cursor=<x-next-cursor of the previous page>
+/// ```
+///
+/// <div class="info">
+/// The compatibility score is calculated based on how strongly a user's tags oppose the
+/// current user's tags. Users who have already been liked or disliked will not appear in results.
+/// Users holding tags the current user has disliked on others (see
+/// `POST /user/{target_user_id}/dislike/tags`) are ranked lower.
+///
+/// Pages are keyed on (score, user id): pass the `x-next-cursor` response header back as
+/// `cursor` to get the next page without duplicates or gaps when scores shift between requests.
+///
+/// Pass `explain=true` to include a `score_breakdown` with each component of the score, the
+/// weights applied to them, and the most strongly opposed tag pairs.
+///
+/// Use `require_tags` and `exclude_tags` to only get candidates holding, or not holding, some
+/// tags, e.g. `GET /nemeses?require_tags=vim` to look for rivals among vim users only, and
+/// `min_score` to skip weak candidates.
+///
+/// Admins can override the configured ranking strategy, weights and fallback score for a single
+/// request, e.g. `GET /nemeses?strategy=tags&explain=true`. Other users get a 403 when passing
+/// any override.
+/// </div>
+#[utoipa::path(
+    get,
+    path="/nemeses",
+    tag=crate::tags::USER,
+    params(PaginationParams, NemesisFilterParams, ExplainParams, RankingOverrideParams),
+    responses(
+        (status = 200, description = "Get potential nemeses for the current user", body = Vec<UserWithTags>, headers(
+            ("x-next-cursor" = String, description = "Cursor of the next page, absent on the last page")
+        )),
+        (status = 400, description = "Invalid pagination cursor, filter or ranking override", body = Error, example = json!(Error::Validation { field: "cursor".to_string(), message: "Invalid pagination cursor".to_string() })),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Ranking overrides are restricted to admins", body = Error, example = json!(Error::Forbidden { reason: "Only admins can override nemesis ranking".to_string() })),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn get_potential_nemeses(
+    State(state): State<ArchenemyState>,
+    user: AuthUser,
+    Query(pagination): Query<PaginationParams>,
+    Query(filter): Query<NemesisFilterParams>,
+    Query(explain): Query<ExplainParams>,
+    Query(overrides): Query<RankingOverrideParams>,
+) -> Result<(HeaderMap, Json<Vec<UserWithTags>>), Error> {
+    let user_id = user.user_id;
+    let explain = explain.explain.unwrap_or(false);
+    let limit = pagination.limit.unwrap_or(10);
+    let offset = pagination.offset();
+    let cursor = pagination.cursor::<f64>()?;
+    let filter = filter.filter()?;
+
+    let ranking = if overrides.is_empty() {
+        state.ranking.clone()
+    } else if utils::is_admin(&state.pool, &user_id).await? {
+        overrides.apply(&state.ranking)?
+    } else {
+        return Err(Error::Forbidden {
+            reason: "Only admins can override nemesis ranking".to_string(),
+        });
+    };
+    let ranker = utils::ranker_for(&ranking);
+
+    let potential_nemeses = utils::get_potential_nemeses(
+        &state.pool,
+        state.embedder.as_ref(),
+        ranker.as_ref(),
+        &filter,
+        &user_id,
+        limit,
+        offset,
+        cursor.as_ref(),
+    )
+    .await?;
+
+    let headers = page_headers(
+        potential_nemeses.len(),
+        limit,
+        potential_nemeses
+            .last()
+            .map(|(user, score, _)| Cursor::new(*score, &user.id)),
+    );
+
+    let (users, scores): (Vec<_>, Vec<_>) = potential_nemeses
+        .into_iter()
+        .map(|(user, score, components)| (user, (score, components)))
+        .unzip();
+    let profiles = utils::public_profiles(&state.pool, &user_id, users).await?;
+
+    // Opposing tags of every candidate on the page are looked up at once
+    let mut opposing_tags = if explain {
+        let ids: Vec<String> = profiles.iter().map(|p| p.id.clone()).collect();
+        utils::get_opposing_tag_pairs(&state.pool, &user_id, &ids, &ranker.weights(), 5).await?
+    } else {
+        Default::default()
+    };
+
+    // For each potential nemesis, get their tags
+    let mut result = Vec::new();
+    for (user, (score, components)) in profiles.into_iter().zip(scores) {
+        let tags = utils::get_user_tags(&state.pool, &user.id).await?;
+
+        let score_breakdown = if explain {
+            Some(ScoreBreakdown {
+                strategy: ranker.strategy(),
+                components,
+                weights: ranker.weights(),
+                opposing_tags: opposing_tags.remove(&user.id).unwrap_or_default(),
+            })
+        } else {
+            None
+        };
+
+        result.push(UserWithTags {
+            user,
+            tags,
+            compatibility_score: Some(score),
+            score_breakdown,
+        });
+    }
+
+    Ok((headers, Json(result)))
+}
+
+/// Like a user to indicate interest in establishing a nemesis relationship.
+///
+/// This endpoint allows the current user to express interest in another user as a potential
+/// nemesis. If both users like each other, they become matched nemeses.
+///
+/// Example:
+/// ```text
+/// POST /user/abc123def456/like
+/// ```
+///
+/// <div class="warning">
+/// Users cannot like themselves. If the target user has already liked the current user,
+/// this will create a match.
+/// </div>
+#[utoipa::path(
+    post,
+    path="/user/{target_user_id}/like",
+    tag=crate::tags::USER,
+    params(
+        ("target_user_id" = String, Path, description = "ID of the user to like")
+    ),
+    responses(
+        (status = 200, description = "User successfully liked, `matched` is true if the target user had already liked the current user", body = LikeResponse),
+        (status = 400, description = "Cannot like yourself", body = Error, example = json!(Error::Validation { field: "target_user_id".to_string(), message: "Cannot like yourself".to_string() })),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Target user not found", body = Error, example = json!(Error::NotFound { resource: "User with ID abc123def456".to_string() })),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn like_user(
+    State(state): State<ArchenemyState>,
+    user: AuthUser,
+    Path(target_user_id): Path<String>,
+) -> Result<Json<LikeResponse>, Error> {
+    let user_id = user.user_id;
+
+    ensure_not_suspended(&state, &user_id).await?;
+
+    // Verify target user exists and the two users haven't blocked each other
+    let maybe_target =
+        utils::get_visible_user_by_id(&state.pool, &user_id, &target_user_id).await?;
+    if maybe_target.is_none() {
+        return Err(Error::NotFound {
+            resource: format!("User with ID {}", target_user_id),
+        });
+    }
+
+    // Prevent liking oneself
+    if user_id == target_user_id {
+        return Err(Error::Validation {
+            field: "target_user_id".to_string(),
+            message: "Cannot like yourself".to_string(),
+        });
+    }
+
+    let (like, matched) = utils::add_like(&state.pool, &user_id, &target_user_id).await?;
+    Ok(Json(LikeResponse { like, matched }))
+}
+
+/// Dislike a user to indicate lack of interest in establishing a nemesis relationship.
+///
+/// This endpoint allows the current user to express that they are not interested in
+/// another user as a potential nemesis. Disliked users will not appear in future
+/// potential nemesis suggestions.
+///
+/// Example:
+/// ```text
 /// POST /user/abc123def456/dislike
 /// ```
 ///
-/// <div class="warning">
-/// Users cannot dislike themselves. Disliking is permanent and cannot be undone.
+/// <div class="warning">
+/// Users cannot dislike themselves. Disliking can be undone with
+/// `DELETE /user/{target_user_id}/dislike`.
+/// </div>
+#[utoipa::path(
+    post,
+    path="/user/{target_user_id}/dislike",
+    tag=crate::tags::USER,
+    params(
+        ("target_user_id" = String, Path, description = "ID of the user to dislike")
+    ),
+    responses(
+        (status = 200, description = "User successfully disliked", body = UserDislike),
+        (status = 400, description = "Cannot dislike yourself", body = Error, example = json!(Error::Validation { field: "target_user_id".to_string(), message: "Cannot dislike yourself".to_string() })),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Target user not found", body = Error, example = json!(Error::NotFound { resource: "User with ID abc123def456".to_string() })),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn dislike_user(
+    State(state): State<ArchenemyState>,
+    user: AuthUser,
+    Path(target_user_id): Path<String>,
+) -> Result<Json<UserDislike>, Error> {
+    let user_id = user.user_id;
+
+    ensure_not_suspended(&state, &user_id).await?;
+
+    // Verify target user exists and the two users haven't blocked each other
+    let maybe_target =
+        utils::get_visible_user_by_id(&state.pool, &user_id, &target_user_id).await?;
+    if maybe_target.is_none() {
+        return Err(Error::NotFound {
+            resource: format!("User with ID {}", target_user_id),
+        });
+    }
+
+    // Prevent disliking oneself
+    if user_id == target_user_id {
+        return Err(Error::Validation {
+            field: "target_user_id".to_string(),
+            message: "Cannot dislike yourself".to_string(),
+        });
+    }
+
+    let dislike = utils::add_dislike(&state.pool, &user_id, &target_user_id).await?;
+    Ok(Json(dislike))
+}
+
+/// Undo a like of a user.
+///
+/// The user returns to the current user's potential nemeses. If the two users were matched,
+/// the match is dissolved, while the other user's like is kept.
+///
+/// Example:
+/// ```text
+/// DELETE /user/abc123def456/like
+/// ```
+#[utoipa::path(
+    delete,
+    path="/user/{target_user_id}/like",
+    tag=crate::tags::USER,
+    params(
+        ("target_user_id" = String, Path, description = "ID of the user to unlike")
+    ),
+    responses(
+        (status = 204, description = "Like successfully removed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "The current user has not liked the target user", body = Error, example = json!(Error::NotFound { resource: "Like of user with ID abc123def456".to_string() })),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn unlike_user(
+    State(state): State<ArchenemyState>,
+    user: AuthUser,
+    Path(target_user_id): Path<String>,
+) -> Result<StatusCode, Error> {
+    let user_id = user.user_id;
+
+    if !utils::remove_like(&state.pool, &user_id, &target_user_id).await? {
+        return Err(Error::NotFound {
+            resource: format!("Like of user with ID {}", target_user_id),
+        });
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Undo a dislike of a user.
+///
+/// The user returns to the current user's potential nemeses, and any tags disliked with
+/// `POST /user/{target_user_id}/dislike/tags` no longer count against other candidates.
+///
+/// Example:
+/// ```text
+/// DELETE /user/abc123def456/dislike
+/// ```
+#[utoipa::path(
+    delete,
+    path="/user/{target_user_id}/dislike",
+    tag=crate::tags::USER,
+    params(
+        ("target_user_id" = String, Path, description = "ID of the user to un-dislike")
+    ),
+    responses(
+        (status = 204, description = "Dislike and its disliked tags successfully removed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "The current user has not disliked the target user", body = Error, example = json!(Error::NotFound { resource: "Dislike of user with ID abc123def456".to_string() })),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn undislike_user(
+    State(state): State<ArchenemyState>,
+    user: AuthUser,
+    Path(target_user_id): Path<String>,
+) -> Result<StatusCode, Error> {
+    let user_id = user.user_id;
+
+    if !utils::remove_dislike(&state.pool, &user_id, &target_user_id).await? {
+        return Err(Error::NotFound {
+            resource: format!("Dislike of user with ID {}", target_user_id),
+        });
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Dislike a user and the specific tags that put the current user off.
+///
+/// This endpoint allows the current user to express that they are not interested in
+/// list of tags as a potential nemesis. The user is disliked as with
+/// `POST /user/{target_user_id}/dislike`, and the tags feed into future nemesis ranking.
+///
+/// Example:
+/// ```text
+/// POST /user/abc123def456/dislike/tags
+/// {
+///   "tag_names": ["boring", "argumentative"]
+/// }
+/// ```
+///
+/// <div class="info">
+/// Disliked tags are treated as negative feedback: other candidates holding any of these tags
+/// are penalized in `GET /nemeses`. The penalty is up to 30% of the score, scaled by the share
+/// of the candidate's tags that were disliked, so a single disliked tag weighs less on someone
+/// with many other tags.
+/// </div>
+#[utoipa::path(
+    post,
+    path="/user/{target_user_id}/dislike/tags",
+    tags=[crate::tags::USER, crate::tags::TAGS],
+    params(
+        ("target_user_id" = String, Path, description = "ID of the user to dislike with tags")
+    ),
+    request_body = AddTagsRequest,
+    responses(
+        (status = 200, description = "User successfully disliked with tags", body = Vec<UserDislikeTag>),
+        (status = 400, description = "Cannot dislike yourself", body = Error, example = json!(Error::Validation { field: "target_user_id".to_string(), message: "Cannot dislike yourself".to_string() })),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Target user not found", body = Error, example = json!(Error::NotFound { resource: "User with ID abc123def456".to_string() })),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+
+)]
+pub async fn dislike_user_with_tags(
+    State(state): State<ArchenemyState>,
+    user: AuthUser,
+    Path(target_user_id): Path<String>,
+    Json(request): Json<AddTagsRequest>,
+) -> Result<Json<Vec<UserDislikeTag>>, Error> {
+    let user_id = user.user_id;
+
+    ensure_not_suspended(&state, &user_id).await?;
+
+    // Verify target user exists and the two users haven't blocked each other
+    let maybe_target =
+        utils::get_visible_user_by_id(&state.pool, &user_id, &target_user_id).await?;
+    if maybe_target.is_none() {
+        return Err(Error::NotFound {
+            resource: format!("User with ID {}", target_user_id),
+        });
+    }
+
+    // Prevent disliking oneself
+    if user_id == target_user_id {
+        return Err(Error::Validation {
+            field: "target_user_id".to_string(),
+            message: "Cannot dislike yourself".to_string(),
+        });
+    }
+
+    // Check every tag before writing any of them
+    let tag_names: Vec<String> = request
+        .tag_names
+        .iter()
+        .filter(|tag_name| !tag_name.is_empty())
+        .map(|tag_name| TagName::normalize(tag_name))
+        .collect();
+    if let Some(banned) = utils::get_banned_tag_names(&state.pool, &tag_names)
+        .await?
+        .first()
+    {
+        return Err(Error::Validation {
+            field: "tag_names".to_string(),
+            message: format!("Tag name {} is not allowed", banned),
+        });
+    }
+
+    // Add a dislike record first (if it doesn't already exist)
+    let _ = utils::add_dislike(&state.pool, &user_id, &target_user_id).await?;
+
+    // Add dislike tags
+    let mut dislike_tags = Vec::new();
+    for tag_name in tag_names {
+        let tag = utils::add_dislike_tag(&state.pool, &user_id, &target_user_id, &tag_name).await?;
+        dislike_tags.push(tag);
+    }
+
+    Ok(Json(dislike_tags))
+}
+
+/// Get a list of users that the current user has liked.
+///
+/// Returns a paginated list of users that the current user has expressed interest in
+/// as potential nemeses, including when they were liked.
+///
+/// Example:
+/// ```text
+/// GET /user/me/likes?limit=10
+/// ```
+///
+/// <div class="info">
+/// Results are sorted by most recent likes first. Each user includes a timestamp
+#[utoipa::path(
+    get,
+    path="/user/me/likes",
+    tag=crate::tags::USER,
+    params(PaginationParams),
+    responses(
+        (status = 200, description = "Get users liked by the current user", body = Vec<UserWithLikedAt>, headers(
+            ("x-next-cursor" = String, description = "Cursor of the next page, absent on the last page")
+        )),
+        (status = 400, description = "Invalid pagination cursor", body = Error, example = json!(Error::Validation { field: "cursor".to_string(), message: "Invalid pagination cursor".to_string() })),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+
+)]
+pub async fn get_liked_users(
+    State(state): State<ArchenemyState>,
+    user: AuthUser,
+    Query(pagination): Query<PaginationParams>,
+) -> Result<(HeaderMap, Json<Vec<UserWithLikedAt>>), Error> {
+    let user_id = user.user_id;
+    let limit = pagination.limit.unwrap_or(10);
+    let offset = pagination.offset();
+    let cursor = pagination.cursor()?;
+
+    let liked_users =
+        utils::get_liked_users(&state.pool, &user_id, limit, offset, cursor.as_ref()).await?;
+
+    let headers = page_headers(
+        liked_users.len(),
+        limit,
+        liked_users
+            .last()
+            .map(|(user, liked_at)| Cursor::new(*liked_at, &user.id)),
+    );
+
+    let (users, liked_ats): (Vec<_>, Vec<_>) = liked_users.into_iter().unzip();
+    let profiles = utils::public_profiles(&state.pool, &user_id, users).await?;
+
+    let result = profiles
+        .into_iter()
+        .zip(liked_ats)
+        .map(|(user, liked_at)| UserWithLikedAt { user, liked_at })
+        .collect();
+
+    Ok((headers, Json(result)))
+}
+
+/// Get a list of users the current user has mutually matched with.
+///
+/// A match is formed when two users have liked each other. Returns a paginated list of
+/// matched users with their tags and when the match was formed.
+///
+/// Example:
+/// ```text
+/// GET /user/me/matches?limit=10
+/// ```
+///
+/// <div class="info">
+/// Results are sorted by most recent matches first.
+/// </div>
+#[utoipa::path(
+    get,
+    path="/user/me/matches",
+    tag=crate::tags::USER,
+    params(PaginationParams),
+    responses(
+        (status = 200, description = "Get users matched with the current user", body = Vec<UserWithMatchedAt>, headers(
+            ("x-next-cursor" = String, description = "Cursor of the next page, absent on the last page")
+        )),
+        (status = 400, description = "Invalid pagination cursor", body = Error, example = json!(Error::Validation { field: "cursor".to_string(), message: "Invalid pagination cursor".to_string() })),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn get_matches(
+    State(state): State<ArchenemyState>,
+    user: AuthUser,
+    Query(pagination): Query<PaginationParams>,
+) -> Result<(HeaderMap, Json<Vec<UserWithMatchedAt>>), Error> {
+    let user_id = user.user_id;
+    let limit = pagination.limit.unwrap_or(10);
+    let offset = pagination.offset();
+    let cursor = pagination.cursor()?;
+
+    let matches = utils::get_matches(&state.pool, &user_id, limit, offset, cursor.as_ref()).await?;
+
+    let headers = page_headers(
+        matches.len(),
+        limit,
+        matches
+            .last()
+            .map(|(user, matched_at)| Cursor::new(*matched_at, &user.id)),
+    );
+
+    let (users, matched_ats): (Vec<_>, Vec<_>) = matches.into_iter().unzip();
+    let profiles = utils::public_profiles(&state.pool, &user_id, users).await?;
+
+    // For each matched user, get their tags
+    let mut result = Vec::new();
+    for (user, matched_at) in profiles.into_iter().zip(matched_ats) {
+        let tags = utils::get_user_tags(&state.pool, &user.id).await?;
+
+        result.push(UserWithMatchedAt {
+            user: UserWithTags {
+                user,
+                tags,
+                compatibility_score: None,
+                score_breakdown: None,
+            },
+            matched_at,
+        });
+    }
+
+    Ok((headers, Json(result)))
+}
+
+/// Get a list of users that the current user has disliked.
+///
+/// Returns a paginated list of users that the current user has expressed no interest in
+/// as potential nemeses, including when they were disliked and any tags associated with
+/// the dislike.
+///
+/// Example:
+/// ```text
+/// GET /user/me/dislikes?limit=10
+/// ```
+///
+/// <div class="info">
+/// Results are sorted by most recent dislikes first. Each user includes a timestamp
+/// indicating when they were disliked and any tags that were associated with the dislike.
+/// </div>
+#[utoipa::path(
+    get,
+    path="/user/me/dislikes",
+    tag=crate::tags::USER,
+    params(PaginationParams),
+    responses(
+        (status = 200, description = "Get users disliked by the current user", body = Vec<UserWithDislikedAt>, headers(
+            ("x-next-cursor" = String, description = "Cursor of the next page, absent on the last page")
+        )),
+        (status = 400, description = "Invalid pagination cursor", body = Error, example = json!(Error::Validation { field: "cursor".to_string(), message: "Invalid pagination cursor".to_string() })),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+
+)]
+pub async fn get_disliked_users(
+    State(state): State<ArchenemyState>,
+    user: AuthUser,
+    Query(pagination): Query<PaginationParams>,
+) -> Result<(HeaderMap, Json<Vec<UserWithDislikedAt>>), Error> {
+    let user_id = user.user_id;
+    let limit = pagination.limit.unwrap_or(10);
+    let offset = pagination.offset();
+    let cursor = pagination.cursor()?;
+
+    let disliked_users =
+        utils::get_disliked_users(&state.pool, &user_id, limit, offset, cursor.as_ref()).await?;
+
+    let headers = page_headers(
+        disliked_users.len(),
+        limit,
+        disliked_users
+            .last()
+            .map(|(user, disliked_at)| Cursor::new(*disliked_at, &user.id)),
+    );
+
+    let (users, disliked_ats): (Vec<_>, Vec<_>) = disliked_users.into_iter().unzip();
+    let profiles = utils::public_profiles(&state.pool, &user_id, users).await?;
+
+    // For each disliked user, get the dislike tags
+    let mut result = Vec::new();
+    for (user, disliked_at) in profiles.into_iter().zip(disliked_ats) {
+        let dislike_tags = utils::get_dislike_tags(&state.pool, &user_id, &user.id).await?;
+
+        result.push(UserWithDislikedAt {
+            user,
+            disliked_at,
+            dislike_tags,
+        });
+    }
+
+    Ok((headers, Json(result)))
+}
+
+/// Block a user.
+///
+/// Blocked users disappear for the current user, and the current user disappears for them:
+/// neither can see the other's profile or tags, find the other among potential nemeses or in
+/// their likes, dislikes and matches, or like or dislike the other. Likes between the two users
+/// and their match, if any, are removed.
+///
+/// Example:
+/// ```text
+/// POST /user/abc123def456/block
+/// ```
+///
+/// <div class="info">
+/// Blocking an already blocked user returns the existing block.
+/// </div>
+#[utoipa::path(
+    post,
+    path="/user/{target_user_id}/block",
+    tag=crate::tags::USER,
+    params(
+        ("target_user_id" = String, Path, description = "ID of the user to block")
+    ),
+    responses(
+        (status = 200, description = "User successfully blocked", body = UserBlock),
+        (status = 400, description = "Cannot block yourself", body = Error, example = json!(Error::Validation { field: "target_user_id".to_string(), message: "Cannot block yourself".to_string() })),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Target user not found", body = Error, example = json!(Error::NotFound { resource: "User with ID abc123def456".to_string() })),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn block_user(
+    State(state): State<ArchenemyState>,
+    user: AuthUser,
+    Path(target_user_id): Path<String>,
+) -> Result<Json<UserBlock>, Error> {
+    let user_id = user.user_id;
+
+    // Verify target user exists, blocking back someone who blocked us is allowed
+    let maybe_target = utils::get_user_by_id(&state.pool, &target_user_id).await?;
+    if maybe_target.is_none() {
+        return Err(Error::NotFound {
+            resource: format!("User with ID {}", target_user_id),
+        });
+    }
+
+    // Prevent blocking oneself
+    if user_id == target_user_id {
+        return Err(Error::Validation {
+            field: "target_user_id".to_string(),
+            message: "Cannot block yourself".to_string(),
+        });
+    }
+
+    let block = utils::add_block(&state.pool, &user_id, &target_user_id).await?;
+    Ok(Json(block))
+}
+
+/// Unblock a user.
+///
+/// The users can see each other again, unless the other user blocked the current user as well.
+/// Likes and matches removed by the block are not restored.
+///
+/// Example:
+/// ```text
+/// DELETE /user/abc123def456/block
+/// ```
+#[utoipa::path(
+    delete,
+    path="/user/{target_user_id}/block",
+    tag=crate::tags::USER,
+    params(
+        ("target_user_id" = String, Path, description = "ID of the user to unblock")
+    ),
+    responses(
+        (status = 204, description = "User successfully unblocked"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "The current user has not blocked the target user", body = Error, example = json!(Error::NotFound { resource: "Block of user with ID abc123def456".to_string() })),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn unblock_user(
+    State(state): State<ArchenemyState>,
+    user: AuthUser,
+    Path(target_user_id): Path<String>,
+) -> Result<StatusCode, Error> {
+    let user_id = user.user_id;
+
+    if !utils::remove_block(&state.pool, &user_id, &target_user_id).await? {
+        return Err(Error::NotFound {
+            resource: format!("Block of user with ID {}", target_user_id),
+        });
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Get a list of users that the current user has blocked.
+///
+/// Returns a paginated list of blocked users, including when they were blocked.
+///
+/// Example:
+/// ```text
+/// GET /user/me/blocks?limit=10
+/// ```
+///
+/// <div class="info">
+/// Results are sorted by most recent blocks first. Users who blocked the current user are not
+/// listed.
+/// </div>
+#[utoipa::path(
+    get,
+    path="/user/me/blocks",
+    tag=crate::tags::USER,
+    params(PaginationParams),
+    responses(
+        (status = 200, description = "Get users blocked by the current user", body = Vec<UserWithBlockedAt>, headers(
+            ("x-next-cursor" = String, description = "Cursor of the next page, absent on the last page")
+        )),
+        (status = 400, description = "Invalid pagination cursor", body = Error, example = json!(Error::Validation { field: "cursor".to_string(), message: "Invalid pagination cursor".to_string() })),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn get_blocked_users(
+    State(state): State<ArchenemyState>,
+    user: AuthUser,
+    Query(pagination): Query<PaginationParams>,
+) -> Result<(HeaderMap, Json<Vec<UserWithBlockedAt>>), Error> {
+    let user_id = user.user_id;
+    let limit = pagination.limit.unwrap_or(10);
+    let offset = pagination.offset();
+    let cursor = pagination.cursor()?;
+
+    let blocked_users =
+        utils::get_blocked_users(&state.pool, &user_id, limit, offset, cursor.as_ref()).await?;
+
+    let headers = page_headers(
+        blocked_users.len(),
+        limit,
+        blocked_users
+            .last()
+            .map(|(user, blocked_at)| Cursor::new(*blocked_at, &user.id)),
+    );
+
+    let (users, blocked_ats): (Vec<_>, Vec<_>) = blocked_users.into_iter().unzip();
+    let profiles = utils::public_profiles(&state.pool, &user_id, users).await?;
+
+    let result = profiles
+        .into_iter()
+        .zip(blocked_ats)
+        .map(|(user, blocked_at)| UserWithBlockedAt { user, blocked_at })
+        .collect();
+
+    Ok((headers, Json(result)))
+}
+
+/// Report a user to the moderators.
+///
+/// Reports go to a moderation queue worked through by admins, who can warn, suspend or delete
+/// the reported user. Reporting doesn't hide the user, block them for that.
+///
+/// Example:
+/// ```text
+/// POST /user/abc123def456/report
+/// {
+///   "reason": "harassment",
+///   "details": "Keeps sending insults about my favorite editor"
+/// }
+/// ```
+///
+/// <div class="info">
+/// Only one pending report per reported user is accepted, further reports are rejected with a
+/// 409 until an admin has resolved or dismissed the first one. `details` is limited to
+/// 1000 characters.
 /// </div>
 #[utoipa::path(
     post,
-    path="/user/{target_user_id}/dislike",
+    path="/user/{target_user_id}/report",
+    tag=crate::tags::USER,
+    params(
+        ("target_user_id" = String, Path, description = "ID of the user to report")
+    ),
+    request_body = ReportRequest,
+    responses(
+        (status = 200, description = "User successfully reported", body = Report),
+        (status = 400, description = "Cannot report yourself, or details too long", body = Error, example = json!(Error::Validation { field: "target_user_id".to_string(), message: "Cannot report yourself".to_string() })),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "The current user is suspended", body = Error, example = json!(Error::Forbidden { reason: "Account suspended until 2025-04-27T00:00:00Z".to_string() })),
+        (status = 404, description = "Target user not found", body = Error, example = json!(Error::NotFound { resource: "User with ID abc123def456".to_string() })),
+        (status = 409, description = "The current user already has a pending report against the target user", body = Error, example = json!(Error::Duplicate { resource: "Pending report against user with ID abc123def456".to_string() })),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn report_user(
+    State(state): State<ArchenemyState>,
+    user: AuthUser,
+    Path(target_user_id): Path<String>,
+    Json(request): Json<ReportRequest>,
+) -> Result<Json<Report>, Error> {
+    let user_id = user.user_id;
+
+    ensure_not_suspended(&state, &user_id).await?;
+
+    // Verify target user exists, users can report someone who blocked them
+    let maybe_target = utils::get_user_by_id(&state.pool, &target_user_id).await?;
+    if maybe_target.is_none() {
+        return Err(Error::NotFound {
+            resource: format!("User with ID {}", target_user_id),
+        });
+    }
+
+    // Prevent reporting oneself
+    if user_id == target_user_id {
+        return Err(Error::Validation {
+            field: "target_user_id".to_string(),
+            message: "Cannot report yourself".to_string(),
+        });
+    }
+
+    let details = request.details.unwrap_or_default();
+    if details.chars().count() > MAX_REPORT_DETAILS_LENGTH {
+        return Err(Error::Validation {
+            field: "details".to_string(),
+            message: format!(
+                "Details cannot be longer than {} characters",
+                MAX_REPORT_DETAILS_LENGTH
+            ),
+        });
+    }
+
+    let report = utils::create_report(
+        &state.pool,
+        &user_id,
+        &target_user_id,
+        request.reason,
+        details.trim(),
+    )
+    .await?
+    .ok_or_else(|| Error::Duplicate {
+        resource: format!("Pending report against user with ID {}", target_user_id),
+    })?;
+
+    Ok(Json(report))
+}
+
+/// Get the warnings moderators sent to the current user.
+///
+/// Example:
+/// ```text
+/// GET /user/me/warnings
+/// ```
+///
+/// <div class="info">
+/// Results are sorted by most recent warnings first. Suspensions come with a warning
+/// explaining them.
+/// </div>
+#[utoipa::path(
+    get,
+    path="/user/me/warnings",
     tag=crate::tags::USER,
+    responses(
+        (status = 200, description = "Get warnings sent to the current user", body = Vec<UserWarning>),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn get_current_user_warnings(
+    State(state): State<ArchenemyState>,
+    user: AuthUser,
+) -> Result<Json<Vec<UserWarning>>, Error> {
+    let warnings = utils::get_user_warnings(&state.pool, &user.user_id).await?;
+    Ok(Json(warnings))
+}
+
+/// List reports in the moderation queue. Admin only.
+///
+/// Example:
+/// ```text
+/// GET /admin/reports?status=open&limit=20
+/// GET /admin/reports?status=open&limit=20&cursor=<x-next-cursor of the previous page>
+/// ```
+///
+/// <div class="info">
+/// Results are sorted by oldest reports first, so the queue is worked through in order.
+/// Without `status`, reports of every status are listed.
+/// </div>
+#[utoipa::path(
+    get,
+    path="/admin/reports",
+    tag=crate::tags::MODERATION,
+    params(PaginationParams, ReportFilterParams),
+    responses(
+        (status = 200, description = "Get reports", body = Vec<Report>, headers(
+            ("x-next-cursor" = String, description = "Cursor of the next page, absent on the last page")
+        )),
+        (status = 400, description = "Invalid pagination cursor", body = Error, example = json!(Error::Validation { field: "cursor".to_string(), message: "Invalid pagination cursor".to_string() })),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "The current user is not an admin", body = Error, example = json!(Error::Forbidden { reason: "Admin role required".to_string() })),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn get_reports(
+    State(state): State<ArchenemyState>,
+    _admin: AdminUser,
+    Query(pagination): Query<PaginationParams>,
+    Query(filter): Query<ReportFilterParams>,
+) -> Result<(HeaderMap, Json<Vec<Report>>), Error> {
+    let limit = pagination.limit.unwrap_or(20);
+    let offset = pagination.offset();
+    let cursor = pagination
+        .cursor::<DateTime<Utc>>()?
+        .map(|c| {
+            c.id.parse::<i32>()
+                .map(|id| (c.key, id))
+                .map_err(|_| Error::Validation {
+                    field: "cursor".to_string(),
+                    message: "Invalid pagination cursor".to_string(),
+                })
+        })
+        .transpose()?;
+
+    let reports = utils::get_reports(&state.pool, filter.status, limit, offset, cursor).await?;
+
+    let headers = page_headers(
+        reports.len(),
+        limit,
+        reports
+            .last()
+            .map(|report| Cursor::new(report.created_at, report.id)),
+    );
+
+    Ok((headers, Json(reports)))
+}
+
+/// Get a single report. Admin only.
+///
+/// Example:
+/// ```text
+/// GET /admin/reports/42
+/// ```
+#[utoipa::path(
+    get,
+    path="/admin/reports/{report_id}",
+    tag=crate::tags::MODERATION,
+    params(
+        ("report_id" = i32, Path, description = "ID of the report")
+    ),
+    responses(
+        (status = 200, description = "Get report of id `report_id`", body = Report),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "The current user is not an admin", body = Error, example = json!(Error::Forbidden { reason: "Admin role required".to_string() })),
+        (status = 404, description = "Report not found", body = Error, example = json!(Error::NotFound { resource: "Report with ID 42".to_string() })),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn get_report(
+    State(state): State<ArchenemyState>,
+    _admin: AdminUser,
+    Path(report_id): Path<i32>,
+) -> Result<Json<Report>, Error> {
+    let report = utils::get_report(&state.pool, report_id)
+        .await?
+        .ok_or_else(|| Error::NotFound {
+            resource: format!("Report with ID {}", report_id),
+        })?;
+
+    Ok(Json(report))
+}
+
+/// Triage, resolve or dismiss a report, and keep notes on it. Admin only.
+///
+/// Example:
+/// ```text
+/// PUT /admin/reports/42
+/// {
+///   "status": "triaged",
+///   "moderator_notes": "Checking their other reports first"
+/// }
+/// ```
+///
+/// <div class="info">
+/// Fields that are not provided keep their current value. Resolving a report here records no
+/// action, use `POST /admin/reports/{report_id}/action` to act on the reported user.
+/// Reports that were acted on can't change status anymore.
+/// </div>
+#[utoipa::path(
+    put,
+    path="/admin/reports/{report_id}",
+    tag=crate::tags::MODERATION,
+    params(
+        ("report_id" = i32, Path, description = "ID of the report")
+    ),
+    request_body = UpdateReportRequest,
+    responses(
+        (status = 200, description = "Report successfully updated", body = Report),
+        (status = 400, description = "The report was already acted on", body = Error, example = json!(Error::Validation { field: "status".to_string(), message: "Report was already acted on".to_string() })),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "The current user is not an admin", body = Error, example = json!(Error::Forbidden { reason: "Admin role required".to_string() })),
+        (status = 404, description = "Report not found", body = Error, example = json!(Error::NotFound { resource: "Report with ID 42".to_string() })),
+        (status = 409, description = "Reopening the report while the reporter has another pending report against the same user", body = Error, example = json!(Error::Duplicate { resource: "Pending report by abc123 against def456".to_string() })),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn update_report(
+    State(state): State<ArchenemyState>,
+    admin: AdminUser,
+    Path(report_id): Path<i32>,
+    Json(request): Json<UpdateReportRequest>,
+) -> Result<Json<Report>, Error> {
+    let report = utils::update_report(
+        &state.pool,
+        report_id,
+        &admin.user_id,
+        request.status,
+        request.moderator_notes.as_deref(),
+    )
+    .await?;
+
+    Ok(Json(report))
+}
+
+/// Act on the user a report is about, and resolve the report. Admin only.
+///
+/// - `warn` sends the user a warning they can read at `GET /user/me/warnings`.
+/// - `suspend` hides the user from everyone and prevents them from liking, disliking and
+///   reporting for `suspend_days` days (7 by default, at most 365), along with a warning.
+/// - `delete` deletes the user's account and uploads once the account deletion grace period is
+///   over, along with a warning. Until then the user is suspended, and can't cancel the
+///   deletion themselves.
+///
+/// Example:
+/// ```text
+/// POST /admin/reports/42/action
+/// {
+///   "action": "suspend",
+///   "message": "Harassing other users is not allowed",
+///   "suspend_days": 14
+/// }
+/// ```
+///
+/// <div class="warning">
+/// Deleting a user cannot be undone once the grace period is over. Only pending (open or triaged) reports can be acted on.
+/// </div>
+#[utoipa::path(
+    post,
+    path="/admin/reports/{report_id}/action",
+    tag=crate::tags::MODERATION,
+    params(
+        ("report_id" = i32, Path, description = "ID of the report")
+    ),
+    request_body = ReportActionRequest,
+    responses(
+        (status = 200, description = "Action taken and report resolved", body = Report),
+        (status = 400, description = "The report is no longer pending, or invalid suspension length", body = Error, example = json!(Error::Validation { field: "status".to_string(), message: "Only pending reports can be acted on".to_string() })),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "The current user is not an admin", body = Error, example = json!(Error::Forbidden { reason: "Admin role required".to_string() })),
+        (status = 404, description = "Report not found, or the reported user no longer exists", body = Error, example = json!(Error::NotFound { resource: "Report with ID 42".to_string() })),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn action_report(
+    State(state): State<ArchenemyState>,
+    admin: AdminUser,
+    Path(report_id): Path<i32>,
+    Json(request): Json<ReportActionRequest>,
+) -> Result<Json<Report>, Error> {
+    let suspend_days = request.suspend_days.unwrap_or(7);
+    if !(1..=MAX_SUSPEND_DAYS).contains(&suspend_days) {
+        return Err(Error::Validation {
+            field: "suspend_days".to_string(),
+            message: format!("Suspensions last between 1 and {} days", MAX_SUSPEND_DAYS),
+        });
+    }
+    let suspended_until = Utc::now() + Duration::from_secs(suspend_days as u64 * 24 * 60 * 60);
+
+    let message = request.message.unwrap_or_else(|| match request.action {
+        ModerationAction::Suspend => format!(
+            "Your account has been suspended until {} for violating the community guidelines",
+            suspended_until.to_rfc3339()
+        ),
+        _ => "You have been reported for violating the community guidelines".to_string(),
+    });
+
+    let report = utils::action_report(
+        &state.pool,
+        report_id,
+        &admin.user_id,
+        request.action,
+        &message,
+        suspended_until,
+        state.account_deletion_grace,
+    )
+    .await?;
+
+    Ok(Json(report))
+}
+
+/// Delete the accounts past their grace period now. Admin only.
+///
+/// Their uploads are removed from the bucket first. Accounts are otherwise deleted
+/// periodically in the background.
+///
+/// Example:
+/// ```text
+/// POST /admin/accounts/purge
+/// ```
+#[utoipa::path(
+    post,
+    path="/admin/accounts/purge",
+    tags=[crate::tags::USER, crate::tags::MODERATION],
+    responses(
+        (status = 200, description = "Accounts past their grace period deleted", body = AccountPurge),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "The current user is not an admin", body = Error, example = json!(Error::Forbidden { reason: "Admin role required".to_string() })),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn purge_deleted_users(
+    State(state): State<ArchenemyState>,
+    _admin: AdminUser,
+) -> Result<Json<AccountPurge>, Error> {
+    let (deleted, failed) = utils::purge_deleted_users(&state.pool, &state.bucket).await?;
+    Ok(Json(AccountPurge {
+        deleted,
+        failed: failed.into_iter().map(|(user_id, _)| user_id).collect(),
+    }))
+}
+
+/// List every tag alias. Admin only.
+///
+/// Example:
+/// ```text
+/// GET /admin/tags/aliases
+/// ```
+#[utoipa::path(
+    get,
+    path="/admin/tags/aliases",
+    tags=[crate::tags::TAGS, crate::tags::MODERATION],
+    responses(
+        (status = 200, description = "Get all tag aliases", body = Vec<TagAlias>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "The current user is not an admin", body = Error, example = json!(Error::Forbidden { reason: "Admin role required".to_string() })),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn get_tag_aliases(
+    State(state): State<ArchenemyState>,
+    _admin: AdminUser,
+) -> Result<Json<Vec<TagAlias>>, Error> {
+    let aliases = utils::get_tag_aliases(&state.pool).await?;
+    Ok(Json(aliases))
+}
+
+/// Make a tag name an alias of a canonical tag. Admin only.
+///
+/// Once aliased, adding, disliking, searching or finding nemesis tags for the alias acts on
+/// the canonical tag instead, e.g. users adding `rust-lang` get `rust`.
+///
+/// Example:
+/// ```text
+/// PUT /admin/tags/aliases/rust-lang
+/// {
+///   "tag_name": "rust"
+/// }
+/// ```
+///
+/// <div class="info">
+/// Both names are case-folded. If `tag_name` is itself an alias, the alias points at its
+/// canonical tag. Aliases of the aliased tag are moved to the canonical tag as well.
+/// </div>
+///
+/// <div class="warning">
+/// Tags that users hold or disliked can't become aliases, they have to be merged instead
+/// with `POST /admin/tags/{tag_name}/merge`.
+/// </div>
+#[utoipa::path(
+    put,
+    path="/admin/tags/aliases/{alias}",
+    tags=[crate::tags::TAGS, crate::tags::MODERATION],
     params(
-        ("target_user_id" = String, Path, description = "ID of the user to dislike")
+        ("alias" = String, Path, description = "The alternative tag name")
     ),
+    request_body = TagAliasRequest,
     responses(
-        (status = 200, description = "User successfully disliked", body = UserDislike),
-        (status = 400, description = "Cannot dislike yourself", body = Error, example = json!(Error::Validation { field: "target_user_id".to_string(), message: "Cannot dislike yourself".to_string() })),
+        (status = 200, description = "Alias successfully set", body = TagAlias),
+        (status = 400, description = "Invalid tag name, or a tag aliased to itself", body = Error, example = json!(Error::Validation { field: "alias".to_string(), message: "A tag cannot be an alias of itself".to_string() })),
         (status = 401, description = "Unauthorized"),
-        (status = 404, description = "Target user not found", body = Error, example = json!(Error::NotFound { resource: "User with ID abc123def456".to_string() })),
+        (status = 403, description = "The current user is not an admin", body = Error, example = json!(Error::Forbidden { reason: "Admin role required".to_string() })),
+        (status = 404, description = "Canonical tag not found", body = Error, example = json!(Error::NotFound { resource: "Tag rust".to_string() })),
+        (status = 409, description = "The alias is a tag in use", body = Error, example = json!(Error::Duplicate { resource: "Tag rust-lang is in use".to_string() })),
         (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
     ),
     security(
         ("firebase_auth_token" = [])
     )
 )]
-pub async fn dislike_user(
+pub async fn set_tag_alias(
     State(state): State<ArchenemyState>,
-    user: AuthUser,
-    Path(target_user_id): Path<String>,
-) -> Result<Json<UserDislike>, Error> {
-    let user_id = user.user_id;
+    _admin: AdminUser,
+    Path(alias): Path<String>,
+    Json(request): Json<TagAliasRequest>,
+) -> Result<Json<TagAlias>, Error> {
+    let alias = TagName::new(TagName::normalize(&alias)).map_err(|e| match e {
+        Error::Validation { message, .. } => Error::Validation {
+            field: "alias".to_string(),
+            message,
+        },
+        e => e,
+    })?;
+    let tag_name = utils::canonical_tag_name(&state.pool, &request.tag_name).await?;
 
-    // Verify target user exists
-    let maybe_target = utils::get_user_by_id(&state.pool, &target_user_id).await?;
-    if maybe_target.is_none() {
+    if *alias == tag_name {
+        return Err(Error::Validation {
+            field: "alias".to_string(),
+            message: "A tag cannot be an alias of itself".to_string(),
+        });
+    }
+
+    if !utils::tag_exists(&state.pool, &tag_name).await? {
         return Err(Error::NotFound {
-            resource: format!("User with ID {}", target_user_id),
+            resource: format!("Tag {}", tag_name),
         });
     }
 
-    // Prevent disliking oneself
-    if user_id == target_user_id {
-        return Err(Error::Validation {
-            field: "target_user_id".to_string(),
-            message: "Cannot dislike yourself".to_string(),
+    let alias = utils::set_tag_alias(&state.pool, &alias, &tag_name).await?;
+    Ok(Json(alias))
+}
+
+/// Remove a tag alias. Admin only.
+///
+/// Example:
+/// ```text
+/// DELETE /admin/tags/aliases/rust-lang
+/// ```
+#[utoipa::path(
+    delete,
+    path="/admin/tags/aliases/{alias}",
+    tags=[crate::tags::TAGS, crate::tags::MODERATION],
+    params(
+        ("alias" = String, Path, description = "The alternative tag name")
+    ),
+    responses(
+        (status = 204, description = "Alias successfully removed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "The current user is not an admin", body = Error, example = json!(Error::Forbidden { reason: "Admin role required".to_string() })),
+        (status = 404, description = "Alias not found", body = Error, example = json!(Error::NotFound { resource: "Tag alias rust-lang".to_string() })),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn remove_tag_alias(
+    State(state): State<ArchenemyState>,
+    _admin: AdminUser,
+    Path(alias): Path<String>,
+) -> Result<StatusCode, Error> {
+    let alias = TagName::normalize(&alias);
+
+    if !utils::remove_tag_alias(&state.pool, &alias).await? {
+        return Err(Error::NotFound {
+            resource: format!("Tag alias {}", alias),
         });
     }
 
-    let dislike = utils::add_dislike(&state.pool, &user_id, &target_user_id).await?;
-    Ok(Json(dislike))
+    Ok(StatusCode::NO_CONTENT)
 }
 
-/// Dislike tags so more simmilar tags will show up.
+/// Set the category and parent of a tag. Admin only.
 ///
-/// This endpoint allows the current user to express that they are not interested in
-/// list of tags as a potential nemesis.
+/// Both replace the current values, so leaving one out clears it.
 ///
 /// Example:
-/// ```
-/// POST /user/abc123def456/dislike/tags
+/// ```text
+/// PUT /admin/tags/neovim/classification
 /// {
-///   "tag_names": ["boring", "argumentative"]
+///   "category": null,
+///   "parent_name": "vim"
 /// }
 /// ```
 ///
-/// <div class="warning">
-/// We need to decide whether to recommend more or less in this scenario.
+/// <div class="info">
+/// A tag without a category inherits the category of its closest categorized parent.
+/// Nemesis matching scales the opposition between tags from the same category and from
+/// different categories separately, see `same_category_weight` and `cross_category_weight`.
 /// </div>
 #[utoipa::path(
-    post,
-    path="/user/{target_user_id}/dislike/tags",
-    tags=[crate::tags::USER, crate::tags::TAGS],
+    put,
+    path="/admin/tags/{tag_name}/classification",
+    tags=[crate::tags::TAGS, crate::tags::MODERATION],
     params(
-        ("target_user_id" = String, Path, description = "ID of the user to dislike with tags")
+        ("tag_name" = String, Path, description = "The tag to classify")
     ),
-    request_body = AddTagsRequest,
+    request_body = TagClassificationRequest,
     responses(
-        (status = 200, description = "User successfully disliked with tags", body = Vec<UserDislikeTag>),
-        (status = 400, description = "Cannot dislike yourself", body = Error, example = json!(Error::Validation { field: "target_user_id".to_string(), message: "Cannot dislike yourself".to_string() })),
+        (status = 200, description = "Tag successfully classified", body = TagClassification),
+        (status = 400, description = "The parent would make the tag its own ancestor", body = Error, example = json!(Error::Validation { field: "parent_name".to_string(), message: "Tag 'vim' can't be a descendant of itself".to_string() })),
         (status = 401, description = "Unauthorized"),
-        (status = 404, description = "Target user not found", body = Error, example = json!(Error::NotFound { resource: "User with ID abc123def456".to_string() })),
+        (status = 403, description = "The current user is not an admin", body = Error, example = json!(Error::Forbidden { reason: "Admin role required".to_string() })),
+        (status = 404, description = "Tag, parent tag or category not found", body = Error, example = json!(Error::NotFound { resource: "Tag vim".to_string() })),
         (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
     ),
     security(
         ("firebase_auth_token" = [])
     )
-
 )]
-pub async fn dislike_user_with_tags(
+pub async fn set_tag_classification(
     State(state): State<ArchenemyState>,
-    user: AuthUser,
-    Path(target_user_id): Path<String>,
-    Json(request): Json<AddTagsRequest>,
-) -> Result<Json<Vec<UserDislikeTag>>, Error> {
-    let user_id = user.user_id;
-
-    // Verify target user exists
-    let maybe_target = utils::get_user_by_id(&state.pool, &target_user_id).await?;
-    if maybe_target.is_none() {
+    _admin: AdminUser,
+    Path(tag_name): Path<String>,
+    Json(request): Json<TagClassificationRequest>,
+) -> Result<Json<TagClassification>, Error> {
+    let tag_name = utils::canonical_tag_name(&state.pool, &tag_name).await?;
+    if !utils::tag_exists(&state.pool, &tag_name).await? {
         return Err(Error::NotFound {
-            resource: format!("User with ID {}", target_user_id),
+            resource: format!("Tag {}", tag_name),
         });
     }
 
-    // Prevent disliking oneself
-    if user_id == target_user_id {
-        return Err(Error::Validation {
-            field: "target_user_id".to_string(),
-            message: "Cannot dislike yourself".to_string(),
-        });
+    let category = request.category.as_deref().map(TagName::normalize);
+    if let Some(category) = &category {
+        if !utils::category_exists(&state.pool, category).await? {
+            return Err(Error::NotFound {
+                resource: format!("Tag category {}", category),
+            });
+        }
     }
 
-    // Add a dislike record first (if it doesn't already exist)
-    let _ = utils::add_dislike(&state.pool, &user_id, &target_user_id).await?;
-
-    // Add dislike tags
-    let mut dislike_tags = Vec::new();
-    for tag_name in request.tag_names {
-        if !tag_name.is_empty() {
-            let tag =
-                utils::add_dislike_tag(&state.pool, &user_id, &target_user_id, &tag_name).await?;
-            dislike_tags.push(tag);
+    let parent_name = match request.parent_name.as_deref() {
+        Some(parent_name) => Some(utils::canonical_tag_name(&state.pool, parent_name).await?),
+        None => None,
+    };
+    if let Some(parent_name) = &parent_name {
+        if !utils::tag_exists(&state.pool, parent_name).await? {
+            return Err(Error::NotFound {
+                resource: format!("Tag {}", parent_name),
+            });
         }
     }
 
-    Ok(Json(dislike_tags))
+    let classification = utils::set_tag_classification(
+        &state.pool,
+        &tag_name,
+        category.as_deref(),
+        parent_name.as_deref(),
+    )
+    .await?;
+    Ok(Json(classification))
 }
 
-/// Get a list of users that the current user has liked.
+/// Rebuild the related and opposed tag relations now. Admin only.
 ///
-/// Returns a paginated list of users that the current user has expressed interest in
-/// as potential nemeses, including when they were liked.
+/// Relations are otherwise rebuilt periodically in the background.
 ///
 /// Example:
+/// ```text
+/// POST /admin/tags/relations/rebuild
 /// ```
-/// GET /user/me/likes?limit=10&offset=0
-/// ```
+#[utoipa::path(
+    post,
+    path="/admin/tags/relations/rebuild",
+    tags=[crate::tags::TAGS, crate::tags::MODERATION],
+    responses(
+        (status = 200, description = "Relations successfully rebuilt", body = TagRelationsRebuild),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "The current user is not an admin", body = Error, example = json!(Error::Forbidden { reason: "Admin role required".to_string() })),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn rebuild_tag_relations(
+    State(state): State<ArchenemyState>,
+    _admin: AdminUser,
+) -> Result<Json<TagRelationsRebuild>, Error> {
+    let (related, opposed) =
+        utils::rebuild_tag_relations(&state.pool, MIN_TAG_RELATION_USERS).await?;
+    Ok(Json(TagRelationsRebuild { related, opposed }))
+}
+
+/// Get all banned tag names. Admin only.
 ///
-/// <div class="info">
-/// Results are sorted by most recent likes first. Each user includes a timestamp
+/// Example:
+/// ```text
+/// GET /admin/tags/banned
+/// ```
 #[utoipa::path(
     get,
-    path="/user/me/likes",
-    tag=crate::tags::USER,
-    params(PaginationParams),
+    path="/admin/tags/banned",
+    tags=[crate::tags::TAGS, crate::tags::MODERATION],
     responses(
-        (status = 200, description = "Get users liked by the current user", body = Vec<UserWithLikedAt>),
+        (status = 200, description = "Get all banned tag names", body = Vec<BannedTag>),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "The current user is not an admin", body = Error, example = json!(Error::Forbidden { reason: "Admin role required".to_string() })),
         (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
     ),
     security(
         ("firebase_auth_token" = [])
     )
+)]
+pub async fn get_banned_tags(
+    State(state): State<ArchenemyState>,
+    _admin: AdminUser,
+) -> Result<Json<Vec<BannedTag>>, Error> {
+    let banned = utils::get_banned_tags(&state.pool).await?;
+    Ok(Json(banned))
+}
 
+/// Ban a tag name. Admin only.
+///
+/// The tag and any alias of that name are removed from every user who added or disliked
+/// it, and the name is rejected wherever a tag name is accepted from then on.
+///
+/// Example:
+/// ```text
+/// PUT /admin/tags/banned/some-slur
+/// {
+///   "reason": "Hate speech"
+/// }
+/// ```
+///
+/// <div class="warning">
+/// Removing the tag can't be undone, unbanning the name only allows it again.
+/// </div>
+#[utoipa::path(
+    put,
+    path="/admin/tags/banned/{tag_name}",
+    tags=[crate::tags::TAGS, crate::tags::MODERATION],
+    params(
+        ("tag_name" = String, Path, description = "The tag name to ban")
+    ),
+    request_body = BanTagRequest,
+    responses(
+        (status = 200, description = "Tag name successfully banned", body = BannedTag),
+        (status = 400, description = "Invalid tag name", body = Error, example = json!(Error::Validation { field: "tag_name".to_string(), message: "Tag name cannot be empty".to_string() })),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "The current user is not an admin", body = Error, example = json!(Error::Forbidden { reason: "Admin role required".to_string() })),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
 )]
-pub async fn get_liked_users(
+pub async fn ban_tag(
     State(state): State<ArchenemyState>,
-    user: AuthUser,
-    Query(pagination): Query<PaginationParams>,
-) -> Result<Json<Vec<UserWithLikedAt>>, Error> {
-    let user_id = user.user_id;
-    let limit = pagination.limit.unwrap_or(10);
-    let offset = pagination.offset.unwrap_or(0);
+    admin: AdminUser,
+    Path(tag_name): Path<String>,
+    Json(request): Json<BanTagRequest>,
+) -> Result<Json<BannedTag>, Error> {
+    let tag_name = TagName::normalize(&tag_name);
+    if tag_name.is_empty() {
+        return Err(Error::Validation {
+            field: "tag_name".to_string(),
+            message: "Tag name cannot be empty".to_string(),
+        });
+    }
 
-    let liked_users = utils::get_liked_users(&state.pool, &user_id, limit, offset).await?;
+    let banned = utils::ban_tag(
+        &state.pool,
+        state.embedder.as_ref(),
+        &tag_name,
+        &request.reason,
+        &admin.user_id,
+    )
+    .await?;
+    Ok(Json(banned))
+}
 
-    let result = liked_users
-        .into_iter()
-        .map(|(user, liked_at)| UserWithLikedAt { user, liked_at })
-        .collect();
+/// Lift the ban on a tag name. Admin only.
+///
+/// Example:
+/// ```text
+/// DELETE /admin/tags/banned/some-slur
+/// ```
+#[utoipa::path(
+    delete,
+    path="/admin/tags/banned/{tag_name}",
+    tags=[crate::tags::TAGS, crate::tags::MODERATION],
+    params(
+        ("tag_name" = String, Path, description = "The banned tag name")
+    ),
+    responses(
+        (status = 204, description = "Ban successfully lifted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "The current user is not an admin", body = Error, example = json!(Error::Forbidden { reason: "Admin role required".to_string() })),
+        (status = 404, description = "Tag name not banned", body = Error, example = json!(Error::NotFound { resource: "Banned tag some-slur".to_string() })),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn unban_tag(
+    State(state): State<ArchenemyState>,
+    _admin: AdminUser,
+    Path(tag_name): Path<String>,
+) -> Result<StatusCode, Error> {
+    let tag_name = TagName::normalize(&tag_name);
+
+    if !utils::unban_tag(&state.pool, &tag_name).await? {
+        return Err(Error::NotFound {
+            resource: format!("Banned tag {}", tag_name),
+        });
+    }
 
-    Ok(Json(result))
+    Ok(StatusCode::NO_CONTENT)
 }
 
-/// Get a list of users that the current user has disliked.
+/// Rename a tag. Admin only.
 ///
-/// Returns a paginated list of users that the current user has expressed no interest in
-/// as potential nemeses, including when they were disliked and any tags associated with
-/// the dislike.
+/// Every user who added or disliked the tag gets the new name, and the old name becomes an
+/// alias of it so clients still sending it keep working.
 ///
 /// Example:
-/// ```
-/// GET /user/me/dislikes?limit=10&offset=0
+/// ```text
+/// PUT /admin/tags/javascript/name
+/// {
+///   "new_name": "js"
+/// }
 /// ```
 ///
 /// <div class="info">
-/// Results are sorted by most recent dislikes first. Each user includes a timestamp
-/// indicating when they were disliked and any tags that were associated with the dislike.
+/// The tag keeps its category and parent, and tags filed under it move along.
+/// </div>
+///
+/// <div class="warning">
+/// The new name can't be an existing tag, merge into it with
+/// `POST /admin/tags/{tag_name}/merge` instead.
 /// </div>
 #[utoipa::path(
-    get,
-    path="/user/me/dislikes",
-    tag=crate::tags::USER,
-    params(PaginationParams),
+    put,
+    path="/admin/tags/{tag_name}/name",
+    tags=[crate::tags::TAGS, crate::tags::MODERATION],
+    params(
+        ("tag_name" = String, Path, description = "The tag to rename")
+    ),
+    request_body = RenameTagRequest,
     responses(
-        (status = 200, description = "Get users disliked by the current user", body = Vec<UserWithDislikedAt>),
+        (status = 204, description = "Tag successfully renamed"),
+        (status = 400, description = "Invalid new tag name", body = Error, example = json!(Error::Validation { field: "new_name".to_string(), message: "Tag name can only contain alphanumeric characters and hyphens".to_string() })),
         (status = 401, description = "Unauthorized"),
+        (status = 403, description = "The current user is not an admin", body = Error, example = json!(Error::Forbidden { reason: "Admin role required".to_string() })),
+        (status = 404, description = "Tag not found", body = Error, example = json!(Error::NotFound { resource: "Tag javascript".to_string() })),
+        (status = 409, description = "A tag or an alias of another tag with the new name already exists", body = Error, example = json!(Error::Duplicate { resource: "Tag js".to_string() })),
         (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
     ),
     security(
         ("firebase_auth_token" = [])
     )
-
 )]
-pub async fn get_disliked_users(
+pub async fn rename_tag(
     State(state): State<ArchenemyState>,
-    user: AuthUser,
-    Query(pagination): Query<PaginationParams>,
-) -> Result<Json<Vec<UserWithDislikedAt>>, Error> {
-    let user_id = user.user_id;
-    let limit = pagination.limit.unwrap_or(10);
-    let offset = pagination.offset.unwrap_or(0);
+    _admin: AdminUser,
+    Path(tag_name): Path<String>,
+    Json(request): Json<RenameTagRequest>,
+) -> Result<StatusCode, Error> {
+    let new_name = TagName::new(TagName::normalize(&request.new_name)).map_err(|e| match e {
+        Error::Validation { message, .. } => Error::Validation {
+            field: "new_name".to_string(),
+            message,
+        },
+        e => e,
+    })?;
+    let tag_name = TagName::normalize(&tag_name);
+
+    if !utils::tag_exists(&state.pool, &tag_name).await? {
+        return Err(Error::NotFound {
+            resource: format!("Tag {}", tag_name),
+        });
+    }
 
-    let disliked_users = utils::get_disliked_users(&state.pool, &user_id, limit, offset).await?;
+    if utils::tag_exists(&state.pool, &new_name).await? {
+        return Err(Error::Duplicate {
+            resource: format!("Tag {}", *new_name),
+        });
+    }
 
-    // For each disliked user, get the dislike tags
-    let mut result = Vec::new();
-    for (user, disliked_at) in disliked_users {
-        let dislike_tags = utils::get_dislike_tags(&state.pool, &user_id, &user.id).await?;
+    // Taking over an alias is only fine if it already stood for this tag
+    let canonical = utils::canonical_tag_name(&state.pool, &new_name).await?;
+    if canonical != *new_name && canonical != tag_name {
+        return Err(Error::Duplicate {
+            resource: format!("Tag alias {}", *new_name),
+        });
+    }
 
-        result.push(UserWithDislikedAt {
-            user,
-            disliked_at,
-            dislike_tags,
+    utils::rename_tag(&state.pool, state.embedder.as_ref(), &tag_name, &new_name).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Merge a tag into another. Admin only.
+///
+/// Every user who added or disliked the merged tag gets the other one instead, keeping
+/// their stance if they already had both. The merged name becomes an alias of the other.
+///
+/// Example:
+/// ```text
+/// POST /admin/tags/rust-lang/merge
+/// {
+///   "into": "rust"
+/// }
+/// ```
+///
+/// <div class="info">
+/// Aliases of the merged tag and tags filed under it move to the other tag. Embeddings of
+/// affected users are recomputed.
+/// </div>
+#[utoipa::path(
+    post,
+    path="/admin/tags/{tag_name}/merge",
+    tags=[crate::tags::TAGS, crate::tags::MODERATION],
+    params(
+        ("tag_name" = String, Path, description = "The tag to merge away")
+    ),
+    request_body = MergeTagRequest,
+    responses(
+        (status = 204, description = "Tags successfully merged"),
+        (status = 400, description = "A tag merged into itself", body = Error, example = json!(Error::Validation { field: "into".to_string(), message: "A tag cannot be merged into itself".to_string() })),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "The current user is not an admin", body = Error, example = json!(Error::Forbidden { reason: "Admin role required".to_string() })),
+        (status = 404, description = "Either tag not found", body = Error, example = json!(Error::NotFound { resource: "Tag rust-lang".to_string() })),
+        (status = 500, description = "Internal server error, this usually indicates something wrong with the database", body = Error, example = json!(Error::Database { message: "Database connection failed".to_string() }))
+    ),
+    security(
+        ("firebase_auth_token" = [])
+    )
+)]
+pub async fn merge_tags(
+    State(state): State<ArchenemyState>,
+    _admin: AdminUser,
+    Path(tag_name): Path<String>,
+    Json(request): Json<MergeTagRequest>,
+) -> Result<StatusCode, Error> {
+    let tag_name = TagName::normalize(&tag_name);
+    let into = utils::canonical_tag_name(&state.pool, &request.into).await?;
+
+    if tag_name == into {
+        return Err(Error::Validation {
+            field: "into".to_string(),
+            message: "A tag cannot be merged into itself".to_string(),
         });
     }
 
-    Ok(Json(result))
+    for name in [&tag_name, &into] {
+        if !utils::tag_exists(&state.pool, name).await? {
+            return Err(Error::NotFound {
+                resource: format!("Tag {}", name),
+            });
+        }
+    }
+
+    utils::merge_tags(&state.pool, state.embedder.as_ref(), &tag_name, &into).await?;
+    Ok(StatusCode::NO_CONTENT)
 }
@@ -8,47 +8,82 @@ mod types;
 /// User business logic and database operations
 mod utils;
 
-// TODO: Implement endpoint to check whether user exists (GET /user/{username}/exists)
+pub use handlers::{DEFAULT_ACCOUNT_DELETION_GRACE_PERIOD, MIN_TAG_RELATION_USERS};
+pub use types::{RankingConfig, RankingStrategy, Validate};
+pub use utils::{purge_deleted_users, rebuild_tag_relations, reembed_stale_tags};
+
 /// Configure and return the router for all user-related API endpoints
 ///
 /// This function sets up the OpenApiRouter with all user-related routes including:
 /// - User profile management (get/update)
-/// - Tag management (get/add/remove)
-/// - Nemesis interaction (discover/like/dislike)
-/// - Relationship management (likes/dislikes)
+/// - Tag management (get/search/add/remove)
+/// - Nemesis interaction (discover/like/dislike and undoing likes/dislikes)
+/// - Relationship management (likes/dislikes/matches)
+/// - Blocking, which hides two users from each other everywhere
+/// - Reporting users, and the admin-only moderation queue under /admin
 ///
 /// All routes require authentication via Firebase Auth.
 pub fn routes() -> OpenApiRouter<ArchenemyState> {
     OpenApiRouter::<ArchenemyState>::new()
         // User profile routes
-        // GET/PUT /user/me - Get or update current user profile
+        // GET/PUT/DELETE /user/me - Get, update or delete current user profile
         .routes(routes!(
             handlers::get_current_user,
-            handlers::update_current_user
+            handlers::update_current_user,
+            handlers::delete_current_user
+        ))
+        // GET /user/me/export - Export everything stored about the current user
+        .routes(routes!(handlers::export_current_user))
+        // GET/DELETE /user/me/deletion - Get or cancel the pending deletion of the current user
+        .routes(routes!(
+            handlers::get_current_user_deletion,
+            handlers::cancel_current_user_deletion
+        ))
+        // GET/PUT /user/me/privacy - Get or set who may see each profile field
+        .routes(routes!(
+            handlers::get_current_user_privacy,
+            handlers::update_current_user_privacy
         ))
-        // GET /user/{user_id} - Get another user's profile
+        // GET /user/{user_id} - Get another user's profile, by ID or @username
         .routes(routes!(handlers::get_user))
+        // GET /users/by-username/{username} - Get a user's profile and tags by username
+        .routes(routes!(handlers::get_user_by_username))
+        // GET /user/{username}/exists - Check whether a username is taken
+        .routes(routes!(handlers::username_exists))
         // Tag system routes
-        // GET /tags - Get all tags in the system
+        // GET /tags - Get all tags in the system, optionally in one category
         .routes(routes!(handlers::get_all_tags))
+        // GET /tags/categories - Get all tag categories
+        .routes(routes!(handlers::get_tag_categories))
+        // GET /tags/trending - Get the tags gaining the most users
+        .routes(routes!(handlers::get_trending_tags))
+        // GET /tags/search - Search tags by prefix or similar spelling
+        .routes(routes!(handlers::search_tags))
         // GET /tags/{tag_name}/nemesis - Get semantically opposite tags
         .routes(routes!(handlers::get_nemesis_tags))
-        // GET /user/{user_id}/tags - Get tags for a specific user
+        // POST /tags/nemesis - Get the tags most opposite to a set of tags
+        .routes(routes!(handlers::get_tag_set_nemesis_tags))
+        // GET /tags/{tag_name}/related - Get tags commonly held together with a tag
+        .routes(routes!(handlers::get_related_tags))
+        // GET /tags/{tag_name}/opposed - Get tags held by users who liked holders of a tag
+        .routes(routes!(handlers::get_opposed_tags))
+        // GET /user/{user_id}/tags - Get tags for a specific user, by ID or @username
         .routes(routes!(handlers::get_user_tags))
         // Current user tag management
-        // GET/POST/DELETE /user/me/tags - Manage current user's tags
+        // GET/POST/PUT/DELETE /user/me/tags - Manage current user's tags
         .routes(routes!(
             handlers::add_current_user_tag,
             handlers::get_current_user_tags,
+            handlers::replace_current_user_tags,
             handlers::remove_current_user_tags
         ))
         // Nemesis discovery and interaction
         // GET /nemeses - Get potential nemeses
         .routes(routes!(handlers::get_potential_nemeses))
-        // POST /user/{user_id}/like - Like a potential nemesis
-        .routes(routes!(handlers::like_user))
-        // POST /user/{user_id}/dislike - Dislike a potential nemesis
-        .routes(routes!(handlers::dislike_user))
+        // POST/DELETE /user/{user_id}/like - Like a potential nemesis or undo the like
+        .routes(routes!(handlers::like_user, handlers::unlike_user))
+        // POST/DELETE /user/{user_id}/dislike - Dislike a potential nemesis or undo the dislike
+        .routes(routes!(handlers::dislike_user, handlers::undislike_user))
         // POST /user/{user_id}/dislike/tags - Dislike with specific tags
         .routes(routes!(handlers::dislike_user_with_tags))
         // Relationship history
@@ -56,4 +91,40 @@ pub fn routes() -> OpenApiRouter<ArchenemyState> {
         .routes(routes!(handlers::get_liked_users))
         // GET /user/me/dislikes - Get users the current user has disliked
         .routes(routes!(handlers::get_disliked_users))
+        // GET /user/me/matches - Get users the current user has mutually liked
+        .routes(routes!(handlers::get_matches))
+        // Blocking
+        // POST/DELETE /user/{user_id}/block - Block or unblock a user
+        .routes(routes!(handlers::block_user, handlers::unblock_user))
+        // GET /user/me/blocks - Get users the current user has blocked
+        .routes(routes!(handlers::get_blocked_users))
+        // Moderation
+        // POST /user/{user_id}/report - Report a user to the moderators
+        .routes(routes!(handlers::report_user))
+        // GET /user/me/warnings - Get warnings sent to the current user by moderators
+        .routes(routes!(handlers::get_current_user_warnings))
+        // GET /admin/reports - List the moderation queue (admin only)
+        .routes(routes!(handlers::get_reports))
+        // GET/PUT /admin/reports/{report_id} - Get, triage or resolve a report (admin only)
+        .routes(routes!(handlers::get_report, handlers::update_report))
+        // POST /admin/reports/{report_id}/action - Warn, suspend or delete a reported user (admin only)
+        .routes(routes!(handlers::action_report))
+        // POST /admin/accounts/purge - Delete accounts past their grace period now (admin only)
+        .routes(routes!(handlers::purge_deleted_users))
+        // GET /admin/tags/aliases - List tag aliases (admin only)
+        .routes(routes!(handlers::get_tag_aliases))
+        // PUT/DELETE /admin/tags/aliases/{alias} - Alias a tag name to a canonical tag (admin only)
+        .routes(routes!(handlers::set_tag_alias, handlers::remove_tag_alias))
+        // PUT /admin/tags/{tag_name}/classification - Set the category and parent of a tag (admin only)
+        .routes(routes!(handlers::set_tag_classification))
+        // POST /admin/tags/relations/rebuild - Rebuild related and opposed tags now (admin only)
+        .routes(routes!(handlers::rebuild_tag_relations))
+        // GET /admin/tags/banned - List banned tag names (admin only)
+        .routes(routes!(handlers::get_banned_tags))
+        // PUT/DELETE /admin/tags/banned/{tag_name} - Ban a tag name or lift the ban (admin only)
+        .routes(routes!(handlers::ban_tag, handlers::unban_tag))
+        // PUT /admin/tags/{tag_name}/name - Rename a tag (admin only)
+        .routes(routes!(handlers::rename_tag))
+        // POST /admin/tags/{tag_name}/merge - Merge a tag into another (admin only)
+        .routes(routes!(handlers::merge_tags))
 }
@@ -195,6 +195,109 @@ impl Validate for Url {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Type, ToSchema)]
+#[sqlx(transparent)]
+pub struct Username(String);
+
+/// Usernames that would be confusing in URLs or impersonate the service.
+const RESERVED_USERNAMES: &[&str] = &[
+    "admin",
+    "administrator",
+    "api",
+    "archenemy",
+    "help",
+    "me",
+    "mod",
+    "moderator",
+    "null",
+    "root",
+    "settings",
+    "support",
+    "system",
+    "undefined",
+];
+
+impl Deref for Username {
+    type Target = String;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<String> for Username {
+    fn from(username: String) -> Self {
+        Self::raw(username)
+    }
+}
+
+impl<Inner> Wrapped<Inner> for Username
+where
+    Inner: Display + ToString + Debug,
+{
+    type Error = crate::types::Error;
+
+    fn new(inner: Inner) -> Result<Self, Self::Error> {
+        let username = Self(inner.to_string());
+
+        username.validate().map(|_| username)
+    }
+
+    fn raw(inner: Inner) -> Self {
+        Self(inner.to_string())
+    }
+}
+
+impl Validate for Username {
+    fn validate(&self) -> Result<(), crate::types::Error> {
+        // Letters, digits, underscores and hyphens, starting with a letter or digit
+        let username = self.0.as_str();
+
+        if username.is_empty() {
+            return Err(crate::types::Error::Validation {
+                field: "username".to_string(),
+                message: "Username cannot be empty".to_string(),
+            });
+        }
+
+        // Generated usernames are `user_` and 32 hex digits
+        if username.len() < 3 || username.len() > 40 {
+            return Err(crate::types::Error::Validation {
+                field: "username".to_string(),
+                message: "Username must be between 3 and 40 characters".to_string(),
+            });
+        }
+
+        if !username
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(crate::types::Error::Validation {
+                field: "username".to_string(),
+                message:
+                    "Username can only contain alphanumeric characters, underscores and hyphens"
+                        .to_string(),
+            });
+        }
+
+        if !username.starts_with(|c: char| c.is_ascii_alphanumeric()) {
+            return Err(crate::types::Error::Validation {
+                field: "username".to_string(),
+                message: "Username must start with a letter or digit".to_string(),
+            });
+        }
+
+        if RESERVED_USERNAMES.contains(&username.to_lowercase().as_str()) {
+            return Err(crate::types::Error::Validation {
+                field: "username".to_string(),
+                message: "Username is reserved".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type, ToSchema)]
 #[sqlx(transparent)]
 pub struct TagName(String);
@@ -224,6 +327,15 @@ where
     }
 }
 
+impl TagName {
+    /// Fold a tag name to the form it is stored in.
+    ///
+    /// Tag names are case-insensitive, so `Rust`, ` rust ` and `RUST` are all `rust`.
+    pub fn normalize(name: &str) -> String {
+        name.trim().to_lowercase()
+    }
+}
+
 impl From<String> for TagName {
     fn from(tag_name: String) -> Self {
         Self::raw(tag_name)
@@ -278,6 +390,62 @@ pub struct User {
     pub updated_at: DateTime<Utc>,
 }
 
+/// Who may see a profile field.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum FieldVisibility {
+    /// Every user who can see the profile
+    #[default]
+    Everyone,
+    /// Only users matched with the owner
+    Matches,
+}
+
+/// Who may see each optional field of a user's profile. The ID, username and tags are
+/// always visible, since finding nemeses relies on them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, FromRow, ToSchema)]
+#[serde(default)]
+pub struct ProfilePrivacy {
+    pub display_name: FieldVisibility,
+    pub avatar_url: FieldVisibility,
+    pub bio: FieldVisibility,
+}
+
+/// A user as other users see them.
+///
+/// Unlike [`User`], it leaves out internals like the embedding, and fields the owner hides
+/// from the viewer are absent.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PublicProfile {
+    pub id: String,
+    pub username: String,
+    pub display_name: Option<String>,
+    #[schema(inline)]
+    pub avatar_url: Option<Url>,
+    pub bio: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PublicProfile {
+    /// Project `user` for a viewer, who is matched with them or not.
+    pub fn new(user: User, privacy: &ProfilePrivacy, matched: bool) -> Self {
+        let visible = |visibility: FieldVisibility| match visibility {
+            FieldVisibility::Everyone => true,
+            FieldVisibility::Matches => matched,
+        };
+
+        Self {
+            id: user.id,
+            username: user.username,
+            display_name: user.display_name.filter(|_| visible(privacy.display_name)),
+            avatar_url: Some(user.avatar_url).filter(|_| visible(privacy.avatar_url)),
+            bio: Some(user.bio).filter(|_| visible(privacy.bio)),
+            created_at: user.created_at,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct CreateUserRequest {
     pub id: String,
@@ -303,6 +471,8 @@ pub struct UserTag {
     pub user_id: String,
     pub tag_name: String,
     pub created_at: DateTime<Utc>,
+    /// How strongly the user is for (1 to 3) or against (-1 to -3) the tag
+    pub stance: i16,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
@@ -321,6 +491,14 @@ pub struct UserDislike {
     pub created_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct UserBlock {
+    pub id: i32,
+    pub user_id: String,
+    pub target_user_id: String,
+    pub created_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct UserDislikeTag {
     pub id: i32,
@@ -332,14 +510,416 @@ pub struct UserDislikeTag {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+#[allow(dead_code)]
 pub struct Tag {
     pub name: String,
     pub embedding: Option<Vec<f32>>,
 }
 
+/// An alternative name for a tag, e.g. `rust-lang` for `rust`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct TagAlias {
+    pub alias: String,
+    /// The canonical tag the alias stands for
+    pub tag_name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A tag name banned by an admin.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct BannedTag {
+    pub name: String,
+    pub reason: String,
+    /// Absent once the admin's account was deleted
+    pub banned_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A broad category tags can be filed under, e.g. `programming` or `food`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct TagCategory {
+    pub name: String,
+    pub description: String,
+    /// Number of tags in the category, including tags inheriting it from a parent
+    pub tag_count: i64,
+}
+
+/// Time window over which tag trends are computed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub enum TrendingWindow {
+    #[serde(rename = "24h")]
+    Day,
+    #[default]
+    #[serde(rename = "7d")]
+    Week,
+    #[serde(rename = "30d")]
+    Month,
+}
+
+impl TrendingWindow {
+    /// Length of the window in hours.
+    pub fn hours(self) -> i32 {
+        match self {
+            Self::Day => 24,
+            Self::Week => 7 * 24,
+            Self::Month => 30 * 24,
+        }
+    }
+}
+
+/// A tag with its adoption during a trending window.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct TrendingTag {
+    pub tag_name: String,
+    /// Users who added the tag during the window and still hold it
+    pub new_adopters: i64,
+    /// Users holding the tag now
+    pub user_count: i64,
+    /// New adopters relative to the users holding the tag before the window, absent for tags
+    /// nobody held before
+    pub growth_rate: Option<f64>,
+}
+
+/// How two tags are related through the users holding them.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum TagRelationKind {
+    /// Held together by the same users
+    Related,
+    /// Held by users who liked holders of the other tag
+    Opposed,
+}
+
+/// A tag related to another one, as of the last rebuild of the tag relations.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct TagRelation {
+    /// The related tag
+    pub tag_name: String,
+    /// Number of users backing the relation
+    pub users: i32,
+    /// How many times more often than chance the tags go together
+    pub lift: f64,
+    /// Pointwise mutual information, the log of `lift`: positive when the tags go together
+    /// more often than chance
+    pub pmi: f64,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// Category and parent of a tag.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct TagClassification {
+    pub tag_name: String,
+    /// Category set directly on the tag
+    pub category: Option<String>,
+    /// Broader tag this tag is a specialization of, e.g. `vim` for `neovim`
+    pub parent_name: Option<String>,
+    /// Category of the tag itself or, failing that, of its closest categorized ancestor
+    pub effective_category: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
 pub struct TagCount {
     #[schema(inline)]
     pub tag_name: TagName,
     pub user_count: i64,
 }
+
+/// Weights used to blend the nemesis score components into a single score.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct NemesisWeights {
+    /// Weight of the user embedding opposition
+    pub user_embedding: f64,
+    /// Weight of the average opposition between the two users' tag embeddings
+    pub tag_embedding: f64,
+    /// Weight of the share of tags not held in common
+    pub tag_overlap: f64,
+    /// Weight subtracted for tags the user has disliked on others
+    pub dislike_tag_penalty: f64,
+    /// Scale of the opposition between tags from the same category (vim vs emacs). The
+    /// averaged opposition is capped at 1 whatever the scale.
+    pub same_category: f64,
+    /// Scale of the opposition between tags from different or unknown categories (vim vs pizza)
+    pub cross_category: f64,
+}
+
+impl Default for NemesisWeights {
+    fn default() -> Self {
+        Self {
+            user_embedding: 0.5,
+            tag_embedding: 0.3,
+            tag_overlap: 0.2,
+            dislike_tag_penalty: 0.3,
+            same_category: 1.0,
+            cross_category: 0.5,
+        }
+    }
+}
+
+impl Validate for NemesisWeights {
+    fn validate(&self) -> Result<(), crate::types::Error> {
+        let weights = [
+            ("user_embedding_weight", self.user_embedding),
+            ("tag_embedding_weight", self.tag_embedding),
+            ("tag_overlap_weight", self.tag_overlap),
+            ("dislike_tag_penalty_weight", self.dislike_tag_penalty),
+            ("same_category_weight", self.same_category),
+            ("cross_category_weight", self.cross_category),
+        ];
+
+        for (field, weight) in weights {
+            if !weight.is_finite() || weight < 0.0 {
+                return Err(crate::types::Error::Validation {
+                    field: field.to_string(),
+                    message: "Weight must be a non-negative number".to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Restrictions on which candidates nemesis discovery returns.
+#[derive(Debug, Clone, Default)]
+pub struct NemesisFilter {
+    /// Candidates must hold every one of these tags
+    pub require_tags: Vec<TagName>,
+    /// Candidates must hold none of these tags
+    pub exclude_tags: Vec<TagName>,
+    /// Candidates must score at least this much
+    pub min_score: Option<f64>,
+}
+
+/// Strategy used to rank potential nemeses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RankingStrategy {
+    /// Blend every score component with the configured weights
+    #[default]
+    Blended,
+    /// Rank on how opposite the user embeddings are, ignoring individual tags
+    Embedding,
+    /// Rank on tags only, ignoring the user embeddings
+    Tags,
+}
+
+/// Configuration of nemesis ranking.
+///
+/// Loaded once at startup from the `RANKING_CONFIG` secret (JSON, every field optional),
+/// and can be overridden per request by admins.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct RankingConfig {
+    /// Which ranking strategy to use
+    pub strategy: RankingStrategy,
+    /// Weights of the score components. The embedding and tags strategies fix the
+    /// user embedding, tag embedding and tag overlap weights and only use the others
+    pub weights: NemesisWeights,
+    /// Value used for a score component that can't be computed, e.g. for a user without tags
+    pub fallback_score: f64,
+}
+
+impl Default for RankingConfig {
+    fn default() -> Self {
+        Self {
+            strategy: RankingStrategy::default(),
+            weights: NemesisWeights::default(),
+            fallback_score: 0.5,
+        }
+    }
+}
+
+impl Validate for RankingConfig {
+    fn validate(&self) -> Result<(), crate::types::Error> {
+        self.weights.validate()?;
+
+        if !(0.0..=1.0).contains(&self.fallback_score) {
+            return Err(crate::types::Error::Validation {
+                field: "fallback_score".to_string(),
+                message: "Fallback score must be between 0 and 1".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Individual components of a nemesis score, each on a 0-1 scale.
+///
+/// Higher values mean a better nemesis match, except for `dislike_tag_penalty` which is
+/// subtracted from the score.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ScoreComponents {
+    pub user_embedding: f64,
+    pub tag_embedding: f64,
+    pub tag_overlap: f64,
+    pub dislike_tag_penalty: f64,
+}
+
+/// A pair of tags, one from each user, and how strongly they oppose each other.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct OpposingTagPair {
+    /// Tag held by the requesting user
+    pub tag_name: String,
+    /// Tag held by the candidate
+    pub opposing_tag_name: String,
+    /// 0-1 scale where 1 = perfect opposites, scaled by the category weight of the pair
+    pub opposition: f64,
+}
+
+/// Keyset pagination cursor pointing at the last item of a page.
+///
+/// Pages are ordered by a sort key (score or timestamp) and then by user id, so the next page
+/// starts right after `(key, id)` even if rows were added or rescored in between. Clients only
+/// ever see the opaque string produced by [`Cursor::encode`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cursor<K> {
+    pub key: K,
+    pub id: String,
+}
+
+/// A sort key that can be losslessly round-tripped through a cursor.
+pub trait CursorKey: Sized {
+    fn to_cursor_string(&self) -> String;
+    fn from_cursor_string(value: &str) -> Option<Self>;
+}
+
+impl CursorKey for f64 {
+    // Store the raw bits so the exact score survives the round trip
+    fn to_cursor_string(&self) -> String {
+        format!("{:016x}", self.to_bits())
+    }
+
+    fn from_cursor_string(value: &str) -> Option<Self> {
+        u64::from_str_radix(value, 16).ok().map(f64::from_bits)
+    }
+}
+
+impl CursorKey for DateTime<Utc> {
+    // Postgres timestamps have microsecond precision
+    fn to_cursor_string(&self) -> String {
+        self.timestamp_micros().to_string()
+    }
+
+    fn from_cursor_string(value: &str) -> Option<Self> {
+        value.parse().ok().and_then(DateTime::from_timestamp_micros)
+    }
+}
+
+impl<K: CursorKey> Cursor<K> {
+    pub fn new(key: K, id: impl ToString) -> Self {
+        Self {
+            key,
+            id: id.to_string(),
+        }
+    }
+
+    /// Encode the cursor as an opaque, URL and header safe string.
+    pub fn encode(&self) -> String {
+        format!("{}:{}", self.key.to_cursor_string(), self.id)
+            .bytes()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    /// Decode a cursor previously produced by [`Cursor::encode`].
+    pub fn decode(value: &str) -> Result<Self, crate::types::Error> {
+        let invalid = || crate::types::Error::Validation {
+            field: "cursor".to_string(),
+            message: "Invalid pagination cursor".to_string(),
+        };
+
+        if !value.len().is_multiple_of(2) {
+            return Err(invalid());
+        }
+
+        let bytes = (0..value.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(value.get(i..i + 2)?, 16).ok())
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(invalid)?;
+        let decoded = String::from_utf8(bytes).map_err(|_| invalid())?;
+
+        let (key, id) = decoded.split_once(':').ok_or_else(invalid)?;
+        let key = K::from_cursor_string(key).ok_or_else(invalid)?;
+
+        Ok(Self::new(key, id))
+    }
+}
+
+/// Why a user was reported.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum ReportReason {
+    Harassment,
+    Spam,
+    Impersonation,
+    InappropriateContent,
+    HateSpeech,
+    Other,
+}
+
+/// Where a report is in the moderation queue.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum ReportStatus {
+    /// Waiting for an admin to look at it
+    Open,
+    /// Seen by an admin, pending a decision
+    Triaged,
+    /// Closed, possibly with an action against the reported user
+    Resolved,
+    /// Closed without action
+    Dismissed,
+}
+
+impl ReportStatus {
+    /// Whether the report still awaits a decision.
+    pub fn is_pending(&self) -> bool {
+        matches!(self, Self::Open | Self::Triaged)
+    }
+}
+
+/// Action taken against a reported user.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum ModerationAction {
+    /// Send the user a warning
+    Warn,
+    /// Hide the user and block their interactions for a while
+    Suspend,
+    /// Delete the user's account
+    Delete,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct Report {
+    pub id: i32,
+    /// Absent once the reporter deleted their account
+    pub reporter_id: Option<String>,
+    /// Absent once the reported user's account was deleted
+    pub reported_user_id: Option<String>,
+    pub reason: ReportReason,
+    pub details: String,
+    pub status: ReportStatus,
+    pub action: Option<ModerationAction>,
+    pub moderator_notes: Option<String>,
+    pub handled_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, ToSchema)]
+pub struct UserWarning {
+    pub id: i32,
+    pub user_id: String,
+    pub report_id: Option<i32>,
+    pub message: String,
+    pub created_at: DateTime<Utc>,
+}
@@ -1,11 +1,17 @@
 use pgvector::Vector;
+use s3::Bucket;
 use sqlx::{self, postgres::PgPool};
 use uuid::Uuid;
 
+use crate::embedding::{normalize, EmbeddingProvider, EMBEDDING_DIM};
+use crate::storage;
 use crate::types::Error;
 use crate::user::types::{
-    CreateUserRequest, TagCount, TagName, UpdateUserRequest, Url, User, UserDislike,
-    UserDislikeTag, UserLike, UserTag, Wrapped,
+    BannedTag, CreateUserRequest, Cursor, FieldVisibility, ModerationAction, NemesisFilter,
+    NemesisWeights, OpposingTagPair, ProfilePrivacy, PublicProfile, RankingConfig, RankingStrategy,
+    Report, ReportReason, ReportStatus, ScoreComponents, TagAlias, TagCategory, TagClassification,
+    TagCount, TagName, TagRelation, TagRelationKind, TrendingTag, UpdateUserRequest, Url, User,
+    UserBlock, UserDislike, UserDislikeTag, UserLike, UserTag, UserWarning, Wrapped,
 };
 
 type Result<T> = std::result::Result<T, Error>;
@@ -19,6 +25,185 @@ pub async fn get_user_by_id(pool: &PgPool, id: &str) -> Result<Option<User>> {
     Ok(user)
 }
 
+// Get a user as seen by another user, users who blocked each other don't see each other at all
+// and suspended users or accounts being deleted are hidden from everyone
+pub async fn get_visible_user_by_id(
+    pool: &PgPool,
+    viewer_id: &str,
+    id: &str,
+) -> Result<Option<User>> {
+    let user = sqlx::query_as!(
+        User,
+        r#"
+        SELECT u.id, u.username, u.avatar_url as "avatar_url: Url", u.bio, u.display_name, u.embedding as "embedding: Vector", u.created_at, u.updated_at
+        FROM Users u
+        WHERE u.id = $2
+            AND user_is_active(u)
+            AND NOT EXISTS (
+                SELECT 1 FROM UserBlocks b
+                WHERE (b.user_id = $1 AND b.target_user_id = u.id)
+                    OR (b.user_id = u.id AND b.target_user_id = $1)
+            )
+        "#,
+        viewer_id,
+        id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(user)
+}
+
+// Like get_visible_user_by_id, but looking the user up by username, ignoring case
+pub async fn get_visible_user_by_username(
+    pool: &PgPool,
+    viewer_id: &str,
+    username: &str,
+) -> Result<Option<User>> {
+    let id = sqlx::query_scalar!(
+        "SELECT id FROM Users WHERE LOWER(username) = LOWER($1)",
+        username
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    match id {
+        Some(id) => get_visible_user_by_id(pool, viewer_id, &id).await,
+        None => Ok(None),
+    }
+}
+
+// Look up a user from a profile link, which holds either their ID or `@` and their username
+pub async fn get_visible_user(
+    pool: &PgPool,
+    viewer_id: &str,
+    id_or_username: &str,
+) -> Result<Option<User>> {
+    match id_or_username.strip_prefix('@') {
+        Some(username) => get_visible_user_by_username(pool, viewer_id, username).await,
+        None => get_visible_user_by_id(pool, viewer_id, id_or_username).await,
+    }
+}
+
+// Project users for a viewer, hiding the fields each user only shows to their matches unless
+// the viewer is one of them. The result has the same order as the input.
+pub async fn public_profiles(
+    pool: &PgPool,
+    viewer_id: &str,
+    users: Vec<User>,
+) -> Result<Vec<PublicProfile>> {
+    use std::collections::HashMap;
+
+    let ids: Vec<String> = users.iter().map(|u| u.id.clone()).collect();
+
+    let rows = sqlx::query!(
+        r#"
+        SELECT
+            u.id,
+            u.display_name_visibility as "display_name_visibility: FieldVisibility",
+            u.avatar_url_visibility as "avatar_url_visibility: FieldVisibility",
+            u.bio_visibility as "bio_visibility: FieldVisibility",
+            (u.id = $1 OR EXISTS (
+                SELECT 1 FROM Matches m
+                WHERE m.user_a_id = LEAST($1 COLLATE "C", u.id COLLATE "C")
+                    AND m.user_b_id = GREATEST($1 COLLATE "C", u.id COLLATE "C")
+            )) as "matched!"
+        FROM Users u
+        WHERE u.id = ANY($2)
+        "#,
+        viewer_id,
+        &ids
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut privacy: HashMap<String, (ProfilePrivacy, bool)> = rows
+        .into_iter()
+        .map(|row| {
+            let privacy = ProfilePrivacy {
+                display_name: row.display_name_visibility,
+                avatar_url: row.avatar_url_visibility,
+                bio: row.bio_visibility,
+            };
+            (row.id, (privacy, row.matched))
+        })
+        .collect();
+
+    let profiles = users
+        .into_iter()
+        .map(|user| {
+            // Users deleted in the meantime show nothing optional
+            let (privacy, matched) = privacy.remove(&user.id).unwrap_or_else(|| {
+                let hidden = FieldVisibility::Matches;
+                (
+                    ProfilePrivacy {
+                        display_name: hidden,
+                        avatar_url: hidden,
+                        bio: hidden,
+                    },
+                    false,
+                )
+            });
+            PublicProfile::new(user, &privacy, matched)
+        })
+        .collect();
+
+    Ok(profiles)
+}
+
+// Project a single user for a viewer, see public_profiles
+pub async fn public_profile(pool: &PgPool, viewer_id: &str, user: User) -> Result<PublicProfile> {
+    let mut profiles = public_profiles(pool, viewer_id, vec![user]).await?;
+    Ok(profiles.remove(0))
+}
+
+pub async fn get_profile_privacy(pool: &PgPool, user_id: &str) -> Result<ProfilePrivacy> {
+    let privacy = sqlx::query_as!(
+        ProfilePrivacy,
+        r#"
+        SELECT
+            display_name_visibility as "display_name: FieldVisibility",
+            avatar_url_visibility as "avatar_url: FieldVisibility",
+            bio_visibility as "bio: FieldVisibility"
+        FROM Users
+        WHERE id = $1
+        "#,
+        user_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(privacy)
+}
+
+pub async fn set_profile_privacy(
+    pool: &PgPool,
+    user_id: &str,
+    privacy: &ProfilePrivacy,
+) -> Result<ProfilePrivacy> {
+    let privacy = sqlx::query_as!(
+        ProfilePrivacy,
+        r#"
+        UPDATE Users
+        SET display_name_visibility = $2, avatar_url_visibility = $3, bio_visibility = $4,
+            updated_at = NOW()
+        WHERE id = $1
+        RETURNING
+            display_name_visibility as "display_name: FieldVisibility",
+            avatar_url_visibility as "avatar_url: FieldVisibility",
+            bio_visibility as "bio: FieldVisibility"
+        "#,
+        user_id,
+        privacy.display_name as _,
+        privacy.avatar_url as _,
+        privacy.bio as _
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(privacy)
+}
+
 pub async fn create_user(pool: &PgPool, user: &CreateUserRequest) -> Result<User> {
     let avatar_url = user.avatar_url.clone().unwrap_or(Url::raw(
         "https://archenemy.nyc3.digitaloceanspaces.com/default.jpeg".to_string(),
@@ -47,7 +232,12 @@ pub async fn create_user(pool: &PgPool, user: &CreateUserRequest) -> Result<User
     Ok(result)
 }
 
-pub async fn update_user(pool: &PgPool, id: &str, update: &UpdateUserRequest) -> Result<User> {
+pub async fn update_user(
+    pool: &PgPool,
+    embedder: &dyn EmbeddingProvider,
+    id: &str,
+    update: &UpdateUserRequest,
+) -> Result<User> {
     use sqlx::QueryBuilder;
 
     let mut builder: QueryBuilder<sqlx::Postgres> =
@@ -74,44 +264,726 @@ pub async fn update_user(pool: &PgPool, id: &str, update: &UpdateUserRequest) ->
     builder.push(" RETURNING *");
 
     let query = builder.build_query_as();
-    let result = query.fetch_one(pool).await?;
+    let result = query.fetch_one(pool).await.map_err(|e| match e {
+        sqlx::Error::Database(ref db) if db.is_unique_violation() => Error::Duplicate {
+            resource: format!(
+                "Username {}",
+                update.username.as_deref().unwrap_or_default()
+            ),
+        },
+        e => e.into(),
+    })?;
 
     // Update user's embedding after profile changes
-    let _ = update_user_embedding(pool, id).await;
+    let _ = update_user_embedding(pool, embedder, id).await;
 
     Ok(result)
 }
 
+// Check whether a username is taken, ignoring case
+pub async fn username_exists(pool: &PgPool, username: &str) -> Result<bool> {
+    let result = sqlx::query!(
+        r#"SELECT EXISTS (SELECT 1 FROM Users WHERE LOWER(username) = LOWER($1)) as "exists!""#,
+        username
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(result.exists)
+}
+
 // Tag queries
-pub async fn get_all_tags(pool: &PgPool) -> Result<Vec<TagCount>> {
-    let tags =
-        sqlx::query!("SELECT tag_name, user_count FROM tag_counts ORDER BY user_count DESC",)
-            .fetch_all(pool)
+
+// Resolve tag names to their canonical tags, case-folding them and following aliases.
+// The result has the same order as the input.
+pub async fn canonical_tag_names(pool: &PgPool, names: &[String]) -> Result<Vec<String>> {
+    let normalized: Vec<String> = names.iter().map(|n| TagName::normalize(n)).collect();
+
+    let results = sqlx::query!(
+        r#"
+        SELECT COALESCE(a.tag_name, n.name) as "name!"
+        FROM UNNEST($1::text[]) WITH ORDINALITY AS n(name, ord)
+        LEFT JOIN TagAliases a ON a.alias = n.name
+        ORDER BY n.ord
+        "#,
+        &normalized
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(results.into_iter().map(|v| v.name).collect())
+}
+
+// Resolve a single tag name to its canonical tag
+pub async fn canonical_tag_name(pool: &PgPool, name: &str) -> Result<String> {
+    let names = canonical_tag_names(pool, &[name.to_string()]).await?;
+
+    Ok(names
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| TagName::normalize(name)))
+}
+
+pub async fn get_tag_aliases(pool: &PgPool) -> Result<Vec<TagAlias>> {
+    let aliases = sqlx::query_as!(
+        TagAlias,
+        "SELECT * FROM TagAliases ORDER BY tag_name, alias"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(aliases)
+}
+
+// Make `alias` stand for `tag_name`, both already normalized.
+// Fails with a duplicate error if the alias is a tag someone holds, those have to be merged.
+pub async fn set_tag_alias(pool: &PgPool, alias: &str, tag_name: &str) -> Result<TagAlias> {
+    let mut tx = pool.begin().await?;
+
+    // Taken by the Tags and TagAliases triggers too, so a tag of this name can't be added
+    // until the alias is in place, and is rejected after
+    sqlx::query!("SELECT lock_tag_name($1)", alias)
+        .execute(&mut *tx)
+        .await?;
+
+    let in_use = sqlx::query!(
+        r#"
+        SELECT EXISTS (SELECT 1 FROM UserTags WHERE tag_name = $1)
+            OR EXISTS (SELECT 1 FROM UserDislikeTags WHERE tag_name = $1) as "in_use!"
+        "#,
+        alias
+    )
+    .fetch_one(&mut *tx)
+    .await?
+    .in_use;
+
+    if in_use {
+        return Err(Error::Duplicate {
+            resource: format!("Tag {} is in use", alias),
+        });
+    }
+
+    // Aliases of the alias now stand for the new canonical tag
+    sqlx::query!(
+        "UPDATE TagAliases SET tag_name = $2 WHERE tag_name = $1",
+        alias,
+        tag_name
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!("DELETE FROM Tags WHERE name = $1", alias)
+        .execute(&mut *tx)
+        .await?;
+
+    let result = sqlx::query_as!(
+        TagAlias,
+        r#"
+        INSERT INTO TagAliases (alias, tag_name)
+        VALUES ($1, $2)
+        ON CONFLICT (alias) DO UPDATE SET tag_name = $2
+        RETURNING *
+        "#,
+        alias,
+        tag_name
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(result)
+}
+
+// Remove an alias, returning whether it existed
+pub async fn remove_tag_alias(pool: &PgPool, alias: &str) -> Result<bool> {
+    let result = sqlx::query!("DELETE FROM TagAliases WHERE alias = $1", alias)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn get_banned_tags(pool: &PgPool) -> Result<Vec<BannedTag>> {
+    let banned = sqlx::query_as!(
+        BannedTag,
+        "SELECT name, reason, banned_by, created_at FROM BannedTags ORDER BY name"
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(banned)
+}
+
+// Get which of the given tag names are banned. New tags with a banned name are rejected by
+// the database, this lets callers check a whole batch before writing any of it.
+pub async fn get_banned_tag_names(pool: &PgPool, names: &[String]) -> Result<Vec<String>> {
+    let banned = sqlx::query_scalar!(
+        "SELECT name FROM BannedTags WHERE name = ANY($1) ORDER BY name",
+        names
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(banned)
+}
+
+// Ban a tag name, removing the tag or alias of that name from everyone and recomputing the
+// embeddings of the users who had it. The name must already be normalized.
+pub async fn ban_tag(
+    pool: &PgPool,
+    embedder: &dyn EmbeddingProvider,
+    name: &str,
+    reason: &str,
+    admin_id: &str,
+) -> Result<BannedTag> {
+    let mut tx = pool.begin().await?;
+
+    let banned = sqlx::query_as!(
+        BannedTag,
+        r#"
+        INSERT INTO BannedTags (name, reason, banned_by)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (name) DO UPDATE SET reason = $2, banned_by = $3
+        RETURNING name, reason, banned_by, created_at
+        "#,
+        name,
+        reason,
+        admin_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let holders: Vec<String> =
+        sqlx::query_scalar!("SELECT user_id FROM UserTags WHERE tag_name = $1", name)
+            .fetch_all(&mut *tx)
+            .await?;
+
+    sqlx::query!("DELETE FROM TagAliases WHERE alias = $1", name)
+        .execute(&mut *tx)
+        .await?;
+
+    // Cascades to UserTags, UserDislikeTags, aliases of the tag and its counts
+    sqlx::query!("DELETE FROM Tags WHERE name = $1", name)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    for user_id in holders {
+        let _ = update_user_embedding(pool, embedder, &user_id).await;
+    }
+
+    Ok(banned)
+}
+
+// Lift the ban on a tag name, returning whether it was banned
+pub async fn unban_tag(pool: &PgPool, name: &str) -> Result<bool> {
+    let result = sqlx::query!("DELETE FROM BannedTags WHERE name = $1", name)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+// Move everything attached to tag `from` over to tag `into`, delete `from` and leave it as an
+// alias of `into`. Users holding both keep their stance on `into`. Counts and trends follow
+// through the UserTags triggers. Returns the users who held `from`.
+async fn merge_tag_rows(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    from: &str,
+    into: &str,
+) -> Result<Vec<String>> {
+    // Like set_tag_alias, before anyone can add `from` again
+    sqlx::query!("SELECT lock_tag_name($1)", from)
+        .execute(&mut **tx)
+        .await?;
+
+    let holders: Vec<String> =
+        sqlx::query_scalar!("SELECT user_id FROM UserTags WHERE tag_name = $1", from)
+            .fetch_all(&mut **tx)
             .await?;
 
+    sqlx::query!(
+        r#"
+        INSERT INTO UserTags (user_id, tag_name, stance, created_at)
+        SELECT user_id, $2, stance, created_at FROM UserTags WHERE tag_name = $1
+        ON CONFLICT (user_id, tag_name) DO NOTHING
+        "#,
+        from,
+        into
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO UserDislikeTags (user_id, target_user_id, tag_name, created_at)
+        SELECT user_id, target_user_id, $2, created_at FROM UserDislikeTags WHERE tag_name = $1
+        ON CONFLICT (user_id, target_user_id, tag_name) DO NOTHING
+        "#,
+        from,
+        into
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE TagAliases SET tag_name = $2 WHERE tag_name = $1",
+        from,
+        into
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query!(
+        "UPDATE Tags SET parent_name = $2 WHERE parent_name = $1 AND name != $2",
+        from,
+        into
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    // Cascades to the remaining UserTags, UserDislikeTags and everything derived from them
+    sqlx::query!("DELETE FROM Tags WHERE name = $1", from)
+        .execute(&mut **tx)
+        .await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO TagAliases (alias, tag_name)
+        VALUES ($1, $2)
+        ON CONFLICT (alias) DO UPDATE SET tag_name = $2
+        "#,
+        from,
+        into
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(holders)
+}
+
+// Merge tag `from` into tag `into`, both must exist and be canonical. `from` becomes an alias
+// of `into`, and the embeddings of the users who held `from` are recomputed.
+pub async fn merge_tags(
+    pool: &PgPool,
+    embedder: &dyn EmbeddingProvider,
+    from: &str,
+    into: &str,
+) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    let holders = merge_tag_rows(&mut tx, from, into).await?;
+    tx.commit().await?;
+
+    for user_id in holders {
+        let _ = update_user_embedding(pool, embedder, &user_id).await;
+    }
+
+    Ok(())
+}
+
+// Rename tag `from` to `to`, which must not be a tag yet. The old name becomes an alias of
+// the new one, the category and parent carry over.
+pub async fn rename_tag(
+    pool: &PgPool,
+    embedder: &dyn EmbeddingProvider,
+    from: &str,
+    to: &str,
+) -> Result<()> {
+    let mut tx = pool.begin().await?;
+
+    // The new name may have been an alias of the tag
+    sqlx::query!("DELETE FROM TagAliases WHERE alias = $1", to)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO Tags (name, embedding, embedding_provider, category, parent_name)
+        SELECT $2, $3, $4, category, parent_name FROM Tags WHERE name = $1
+        "#,
+        from,
+        to,
+        embedder.embed(to) as Vector,
+        embedder.name()
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let holders = merge_tag_rows(&mut tx, from, to).await?;
+    tx.commit().await?;
+
+    // The new name embeds differently, so its holders move too
+    for user_id in holders {
+        let _ = update_user_embedding(pool, embedder, &user_id).await;
+    }
+
+    Ok(())
+}
+
+// Check whether a tag exists
+pub async fn tag_exists(pool: &PgPool, name: &str) -> Result<bool> {
+    let result = sqlx::query!(
+        r#"SELECT EXISTS (SELECT 1 FROM Tags WHERE name = $1) as "exists!""#,
+        name
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(result.exists)
+}
+pub async fn get_all_tags(pool: &PgPool, category: Option<&str>) -> Result<Vec<TagCount>> {
+    // Tags without a category of their own are listed under the category of their parent
+    let tags = sqlx::query!(
+        r#"
+        SELECT tc.tag_name, tc.user_count
+        FROM tag_counts tc
+        LEFT JOIN tag_effective_categories tec ON tec.name = tc.tag_name
+        WHERE $1::text IS NULL OR tec.category = $1
+        ORDER BY tc.user_count DESC
+        "#,
+        category
+    )
+    .fetch_all(pool)
+    .await?;
+
     let tags = tags
-        .iter()
-        .filter_map(|v| {
-            if v.tag_name.is_none() {
-                None
-            } else {
-                Some(TagCount {
-                    tag_name: TagName::from(v.tag_name.clone().unwrap()),
-                    user_count: v.user_count.unwrap_or(0),
-                })
-            }
+        .into_iter()
+        .map(|v| TagCount {
+            tag_name: TagName::from(v.tag_name),
+            user_count: v.user_count,
         })
         .collect();
 
     Ok(tags)
 }
 
+// Get every tag category with the number of tags filed under it
+pub async fn get_tag_categories(pool: &PgPool) -> Result<Vec<TagCategory>> {
+    let categories = sqlx::query_as!(
+        TagCategory,
+        r#"
+        SELECT c.name, c.description, COUNT(tec.name) as "tag_count!"
+        FROM TagCategories c
+        LEFT JOIN tag_effective_categories tec ON tec.category = c.name
+        GROUP BY c.name, c.description
+        ORDER BY c.name
+        "#
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(categories)
+}
+
+// Get the tags adopted by the most users during the last `window_hours`, to the hour.
+// Reads the hourly adoption rollup maintained by a trigger on UserTags.
+pub async fn get_trending_tags(
+    pool: &PgPool,
+    window_hours: i32,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<TrendingTag>> {
+    let tags = sqlx::query_as!(
+        TrendingTag,
+        r#"
+        WITH window_adoptions AS (
+            SELECT tag_name, SUM(adopters)::bigint as new_adopters
+            FROM TagAdoptionsHourly
+            WHERE hour >= date_trunc('hour', NOW() - make_interval(hours => $1))
+            GROUP BY tag_name
+            HAVING SUM(adopters) > 0
+        ),
+        totals AS (
+            SELECT w.tag_name, w.new_adopters, SUM(a.adopters)::bigint as user_count
+            FROM window_adoptions w
+            JOIN TagAdoptionsHourly a ON a.tag_name = w.tag_name
+            GROUP BY w.tag_name, w.new_adopters
+        )
+        SELECT
+            tag_name as "tag_name!",
+            new_adopters as "new_adopters!",
+            user_count as "user_count!",
+            new_adopters::float8 / NULLIF(user_count - new_adopters, 0) as growth_rate
+        FROM totals
+        ORDER BY new_adopters DESC, growth_rate DESC NULLS FIRST, tag_name
+        LIMIT $2 OFFSET $3
+        "#,
+        window_hours,
+        limit,
+        offset
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(tags)
+}
+
+// Rebuild the related and opposed tag relations from scratch, only keeping pairs backed by
+// at least `min_users` users. Only users for a tag (positive stance) count as holding it, so
+// fans and critics of a tag aren't mixed up. Readers keep seeing the previous relations until
+// it commits. Returns the number of related and opposed pairs.
+pub async fn rebuild_tag_relations(pool: &PgPool, min_users: i64) -> Result<(u64, u64)> {
+    let mut tx = pool.begin().await?;
+
+    // Serialize rebuilds, or a concurrent one would insert the same pairs again
+    sqlx::query!("SELECT pg_advisory_xact_lock(hashtext('tag_relations'))")
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query!("DELETE FROM TagRelations")
+        .execute(&mut *tx)
+        .await?;
+
+    // lift = P(both) / (P(a) * P(b)) over all users holding tags
+    let related = sqlx::query!(
+        r#"
+        WITH fans AS (
+            SELECT user_id, tag_name FROM UserTags WHERE stance > 0
+        ),
+        user_total AS (
+            SELECT COUNT(DISTINCT user_id)::float8 AS n FROM fans
+        ),
+        holders AS (
+            SELECT tag_name, COUNT(*)::float8 AS c FROM fans GROUP BY tag_name
+        ),
+        pairs AS (
+            SELECT a.tag_name, b.tag_name AS other_tag_name, COUNT(*) AS users
+            FROM fans a
+            JOIN fans b ON b.user_id = a.user_id AND b.tag_name != a.tag_name
+            GROUP BY a.tag_name, b.tag_name
+            HAVING COUNT(*) >= $1
+        )
+        INSERT INTO TagRelations (tag_name, other_tag_name, kind, users, lift, pmi)
+        SELECT
+            p.tag_name,
+            p.other_tag_name,
+            'related',
+            p.users,
+            p.users * t.n / (ha.c * hb.c),
+            ln(p.users * t.n / (ha.c * hb.c))
+        FROM pairs p
+        CROSS JOIN user_total t
+        JOIN holders ha ON ha.tag_name = p.tag_name
+        JOIN holders hb ON hb.tag_name = p.other_tag_name
+        "#,
+        min_users
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    // lift = P(liker holds b | liked a holder of a) / P(anyone holds b)
+    let opposed = sqlx::query!(
+        r#"
+        WITH fans AS (
+            SELECT user_id, tag_name FROM UserTags WHERE stance > 0
+        ),
+        user_total AS (
+            SELECT COUNT(DISTINCT user_id)::float8 AS n FROM fans
+        ),
+        holders AS (
+            SELECT tag_name, COUNT(*)::float8 AS c FROM fans GROUP BY tag_name
+        ),
+        -- Users who liked at least one holder of each tag
+        tag_likers AS (
+            SELECT DISTINCT ut.tag_name, ul.user_id AS liker_id
+            FROM UserLikes ul
+            JOIN fans ut ON ut.user_id = ul.target_user_id
+        ),
+        liker_counts AS (
+            SELECT tag_name, COUNT(*)::float8 AS c FROM tag_likers GROUP BY tag_name
+        ),
+        pairs AS (
+            SELECT tl.tag_name, lt.tag_name AS other_tag_name, COUNT(*) AS users
+            FROM tag_likers tl
+            JOIN fans lt ON lt.user_id = tl.liker_id AND lt.tag_name != tl.tag_name
+            GROUP BY tl.tag_name, lt.tag_name
+            HAVING COUNT(*) >= $1
+        )
+        INSERT INTO TagRelations (tag_name, other_tag_name, kind, users, lift, pmi)
+        SELECT
+            p.tag_name,
+            p.other_tag_name,
+            'opposed',
+            p.users,
+            (p.users / lc.c) / (hb.c / t.n),
+            ln((p.users / lc.c) / (hb.c / t.n))
+        FROM pairs p
+        CROSS JOIN user_total t
+        JOIN liker_counts lc ON lc.tag_name = p.tag_name
+        JOIN holders hb ON hb.tag_name = p.other_tag_name
+        "#,
+        min_users
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok((related.rows_affected(), opposed.rows_affected()))
+}
+
+// Get the tags most strongly related to a tag in the given way, strongest first
+pub async fn get_tag_relations(
+    pool: &PgPool,
+    tag_name: &str,
+    kind: TagRelationKind,
+    limit: i64,
+) -> Result<Vec<TagRelation>> {
+    let relations = sqlx::query_as!(
+        TagRelation,
+        r#"
+        SELECT other_tag_name as tag_name, users, lift, pmi, computed_at
+        FROM TagRelations
+        WHERE tag_name = $1 AND kind = $2
+        ORDER BY pmi DESC, users DESC, other_tag_name
+        LIMIT $3
+        "#,
+        tag_name,
+        kind as _,
+        limit
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(relations)
+}
+
+// Check whether a tag category exists
+pub async fn category_exists(pool: &PgPool, name: &str) -> Result<bool> {
+    let result = sqlx::query!(
+        r#"SELECT EXISTS (SELECT 1 FROM TagCategories WHERE name = $1) as "exists!""#,
+        name
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(result.exists)
+}
+
+// Set the category and parent of an existing tag, both names must already be canonical.
+// Fails with a validation error if the parent would make the tag its own ancestor.
+pub async fn set_tag_classification(
+    pool: &PgPool,
+    tag_name: &str,
+    category: Option<&str>,
+    parent_name: Option<&str>,
+) -> Result<TagClassification> {
+    let mut tx = pool.begin().await?;
+
+    // Serialize hierarchy changes so two concurrent updates can't build a cycle together
+    sqlx::query!("SELECT pg_advisory_xact_lock(hashtext('tag_hierarchy'))")
+        .execute(&mut *tx)
+        .await?;
+
+    if let Some(parent_name) = parent_name {
+        let creates_cycle = sqlx::query!(
+            r#"
+            WITH RECURSIVE ancestors AS (
+                SELECT name, parent_name FROM Tags WHERE name = $1
+                UNION
+                SELECT t.name, t.parent_name
+                FROM Tags t
+                JOIN ancestors a ON t.name = a.parent_name
+            )
+            SELECT EXISTS (SELECT 1 FROM ancestors WHERE name = $2) as "exists!"
+            "#,
+            parent_name,
+            tag_name
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if creates_cycle.exists {
+            return Err(Error::Validation {
+                field: "parent_name".to_string(),
+                message: format!("Tag '{}' can't be a descendant of itself", tag_name),
+            });
+        }
+    }
+
+    let classification = sqlx::query_as!(
+        TagClassification,
+        r#"
+        WITH updated AS (
+            UPDATE Tags
+            SET category = $2, parent_name = $3
+            WHERE name = $1
+            RETURNING name, category, parent_name
+        )
+        SELECT
+            updated.name as "tag_name!",
+            updated.category,
+            updated.parent_name,
+            -- The view still sees the old row, so resolve the inherited category here
+            COALESCE(updated.category, tec.category) as effective_category
+        FROM updated
+        LEFT JOIN tag_effective_categories tec ON tec.name = updated.parent_name
+        "#,
+        tag_name,
+        category,
+        parent_name
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(classification)
+}
+
+// Search tags by prefix or trigram similarity, and tags with an alias starting with the query.
+// Prefix matches come first, then tags are ranked by popularity and closeness to the query.
+// The query must already be normalized.
+pub async fn search_tags(
+    pool: &PgPool,
+    query: &str,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<TagCount>> {
+    let tags = sqlx::query!(
+        r#"
+        SELECT t.name, COALESCE(tc.user_count, 0) as "user_count!"
+        FROM Tags t
+        LEFT JOIN tag_counts tc ON tc.tag_name = t.name
+        WHERE t.name LIKE $1 || '%'
+            OR t.name % $1
+            OR t.name IN (SELECT a.tag_name FROM TagAliases a WHERE a.alias LIKE $1 || '%')
+        ORDER BY
+            t.name LIKE $1 || '%' DESC,
+            COALESCE(tc.user_count, 0) DESC,
+            similarity(t.name, $1) DESC,
+            t.name ASC
+        LIMIT $2
+        OFFSET $3
+        "#,
+        query,
+        limit,
+        offset
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(tags
+        .into_iter()
+        .map(|v| TagCount {
+            tag_name: TagName::from(v.name),
+            user_count: v.user_count,
+        })
+        .collect())
+}
+
 // Get semantically opposite tags (nemesis tags) based on embedding similarity
 pub async fn get_nemesis_tags(
     pool: &PgPool,
+    embedder: &dyn EmbeddingProvider,
     tag_name: &str,
     limit: i64,
 ) -> Result<Vec<(String, f64)>> {
+    let tag_name = canonical_tag_name(pool, tag_name).await?;
+    let tag_name = tag_name.as_str();
+
     // Get the embedding for the specified tag
     let tag_embedding = sqlx::query!(
         r#"
@@ -122,44 +994,160 @@ pub async fn get_nemesis_tags(
     .fetch_optional(pool)
     .await?;
 
-    // If the tag exists and has an embedding
-    if let Some(embedding) = tag_embedding.and_then(|v| v.embedding) {
-        // Create opposite embedding by negating each value
-        let opposite_embedding: Vec<f32> = embedding.as_slice().iter().map(|&val| -val).collect();
+    // Tags nobody holds yet can still be embedded on the fly
+    let embedding = tag_embedding
+        .and_then(|v| v.embedding)
+        .unwrap_or_else(|| embedder.embed(tag_name));
 
-        // Find opposite tags using cosine similarity with the negated embedding
-        // The closer to 1.0, the more opposite the tag is semantically
-        let results = sqlx::query!(
-            r#"
-                SELECT 
-                    name, 
-                    embedding <=> $1 as nemesis_score
-                FROM 
-                    Tags
-                WHERE 
-                    name != $2 AND embedding IS NOT NULL
-                ORDER BY 
-                    embedding <=> $1
-                LIMIT $3
-                "#,
-            Vector::from(opposite_embedding) as Vector,
-            tag_name,
-            limit
-        )
-        .fetch_all(pool)
-        .await?;
+    // Create opposite embedding by negating each value
+    let opposite_embedding: Vec<f32> = embedding.as_slice().iter().map(|&val| -val).collect();
+
+    // Find opposite tags using cosine similarity with the negated embedding
+    // The closer to 1.0, the more opposite the tag is semantically
+    let results = sqlx::query!(
+        r#"
+            SELECT 
+                name, 
+                embedding <=> $1 as nemesis_score
+            FROM 
+                Tags
+            WHERE 
+                name != $2 AND embedding IS NOT NULL
+            ORDER BY 
+                embedding <=> $1
+            LIMIT $3
+            "#,
+        Vector::from(opposite_embedding) as Vector,
+        tag_name,
+        limit
+    )
+    .fetch_all(pool)
+    .await?;
+
+    // Convert to (String, f32) tuples
+    let nemesis_tags = results
+        .into_iter()
+        .map(|record| (record.name, record.nemesis_score.unwrap_or(f64::NAN)))
+        .collect();
+
+    Ok(nemesis_tags)
+}
+
+// Get the tags most opposite to the combined centroid of a set of (tag, stance), or of the
+// user's own tags when no set is given. Tags in the set and tags the user holds are skipped.
+// Scores go from 0 (same direction as the centroid) to 1 (perfect opposite).
+pub async fn get_nemesis_tags_for_set(
+    pool: &PgPool,
+    embedder: &dyn EmbeddingProvider,
+    user_id: &str,
+    tags: Option<&[(String, Option<i16>)]>,
+    min_score: Option<f64>,
+    limit: i64,
+) -> Result<Vec<(String, f64)>> {
+    let tags: Vec<(String, i16)> = match tags {
+        Some(tags) => {
+            let raw_names: Vec<String> = tags.iter().map(|(name, _)| name.clone()).collect();
+            let names = canonical_tag_names(pool, &raw_names).await?;
+
+            // A tag given twice, directly or through aliases, keeps the last stance given
+            let mut positions: std::collections::HashMap<String, usize> =
+                std::collections::HashMap::new();
+            let mut deduped: Vec<(String, Option<i16>)> = Vec::new();
+            for (name, (_, stance)) in names.into_iter().zip(tags) {
+                match positions.get(&name) {
+                    Some(&i) => deduped[i].1 = stance.or(deduped[i].1),
+                    None => {
+                        positions.insert(name.clone(), deduped.len());
+                        deduped.push((name, *stance));
+                    }
+                }
+            }
 
-        // Convert to (String, f32) tuples
-        let nemesis_tags = results
+            deduped
+                .into_iter()
+                .map(|(name, stance)| (name, stance.unwrap_or(1)))
+                .collect()
+        }
+        None => get_user_tags(pool, user_id)
+            .await?
             .into_iter()
-            .map(|record| (record.name, record.nemesis_score.unwrap_or(f64::NAN)))
-            .collect();
+            .map(|tag| (tag.tag_name, tag.stance))
+            .collect(),
+    };
+
+    if tags.is_empty() {
+        return Err(Error::Validation {
+            field: "tags".to_string(),
+            message: "At least one tag is required".to_string(),
+        });
+    }
+
+    let names: Vec<String> = tags.iter().map(|(name, _)| name.clone()).collect();
+    let stored = sqlx::query!(
+        r#"
+        SELECT name, embedding as "embedding!: Vector"
+        FROM Tags
+        WHERE name = ANY($1) AND embedding IS NOT NULL
+        "#,
+        &names
+    )
+    .fetch_all(pool)
+    .await?;
+
+    // Tags nobody holds yet can still be embedded on the fly
+    let tag_embeddings: Vec<(Vec<f32>, i16)> = tags
+        .iter()
+        .map(|(name, stance)| {
+            let embedding = stored
+                .iter()
+                .find(|t| t.name == *name)
+                .map(|t| t.embedding.to_vec())
+                .unwrap_or_else(|| embedder.embed(name).to_vec());
+            (embedding, *stance)
+        })
+        .collect();
 
-        return Ok(nemesis_tags);
+    // Stances cancelling each other out leave nothing to oppose
+    let centroid = stance_centroid(&tag_embeddings);
+    if centroid.as_slice().iter().all(|&val| val == 0.0) {
+        return Err(Error::Validation {
+            field: "tags".to_string(),
+            message: "The stances on the tags cancel each other out".to_string(),
+        });
     }
 
-    // Tag doesn't exist or has no embedding
-    Ok(Vec::new())
+    // Search with the negated centroid, so the vector index can be used
+    let opposite_centroid: Vec<f32> = centroid.as_slice().iter().map(|&val| -val).collect();
+
+    let results = sqlx::query!(
+        r#"
+        SELECT
+            name,
+            1 - (embedding <=> $1) / 2 as "nemesis_score!"
+        FROM
+            Tags
+        WHERE
+            embedding IS NOT NULL
+            AND NOT (name = ANY($2))
+            AND name NOT IN (SELECT tag_name FROM UserTags WHERE user_id = $3)
+            AND ($4::float8 IS NULL OR 1 - (embedding <=> $1) / 2 >= $4)
+        ORDER BY
+            embedding <=> $1
+        LIMIT $5
+        "#,
+        Vector::from(opposite_centroid) as Vector,
+        &names,
+        user_id,
+        min_score,
+        limit
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(results
+        .into_iter()
+        .map(|record| (record.name, record.nemesis_score))
+        .collect())
 }
 
 pub async fn get_user_tags(pool: &PgPool, user_id: &str) -> Result<Vec<UserTag>> {
@@ -174,89 +1162,345 @@ pub async fn get_user_tags(pool: &PgPool, user_id: &str) -> Result<Vec<UserTag>>
     Ok(tags)
 }
 
-pub async fn add_user_tag(pool: &PgPool, user_id: &str, tag_name: &str) -> Result<UserTag> {
+pub async fn add_user_tag(
+    pool: &PgPool,
+    embedder: &dyn EmbeddingProvider,
+    user_id: &str,
+    tag_name: &str,
+    stance: Option<i16>,
+) -> Result<UserTag> {
+    let tag_name = canonical_tag_name(pool, tag_name).await?;
+    let tag_name = tag_name.as_str();
+
     // Generate tag embedding
-    let tag_embedding = generate_tag_embedding(tag_name);
+    let tag_embedding = embedder.embed(tag_name);
 
-    // First ensure the tag exists in the Tags table with embedding
+    // First ensure the tag exists in the Tags table with an embedding from the current provider
     sqlx::query!(
         r#"
-        INSERT INTO Tags (name, embedding)
-        VALUES ($1, $2)
-        ON CONFLICT (name) DO UPDATE SET embedding = $2 WHERE Tags.embedding IS NULL
+        INSERT INTO Tags (name, embedding, embedding_provider)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (name) DO UPDATE SET embedding = $2, embedding_provider = $3
+        WHERE Tags.embedding IS NULL OR Tags.embedding_provider IS DISTINCT FROM $3
         "#,
         tag_name,
-        tag_embedding as Vector
+        tag_embedding as Vector,
+        embedder.name()
     )
     .execute(pool)
     .await?;
 
-    // Then add the user-tag relationship
+    // Then add the user-tag relationship, or change the stance of a tag the user already has
     let result = sqlx::query_as!(
         UserTag,
         r#"
-        INSERT INTO UserTags (user_id, tag_name)
-        VALUES ($1, $2)
-        ON CONFLICT (user_id, tag_name) DO NOTHING
+        INSERT INTO UserTags (user_id, tag_name, stance)
+        VALUES ($1, $2, COALESCE($3::smallint, 1))
+        ON CONFLICT (user_id, tag_name) DO UPDATE SET stance = COALESCE($3::smallint, UserTags.stance)
         RETURNING *
         "#,
         user_id,
-        tag_name
+        tag_name,
+        stance
     )
     .fetch_one(pool)
     .await?;
 
-    // Refresh the materialized view
-    sqlx::query!("REFRESH MATERIALIZED VIEW tag_counts")
-        .execute(pool)
-        .await?;
-
     // Update user's embedding after adding tag
-    let _ = update_user_embedding(pool, user_id).await;
+    let _ = update_user_embedding(pool, embedder, user_id).await;
 
     Ok(result)
 }
 
-pub async fn remove_user_tag(pool: &PgPool, user_id: &str, names: &[String]) -> Result<()> {
+// Replace all of a user's tags with the given set of (name, stance) in one transaction, then
+// recompute the user's embedding once. Tags without a stance keep the one they have, or get
+// the default. Returns the user's tags afterwards.
+pub async fn set_user_tags(
+    pool: &PgPool,
+    embedder: &dyn EmbeddingProvider,
+    user_id: &str,
+    tags: &[(String, Option<i16>)],
+) -> Result<Vec<UserTag>> {
+    let raw_names: Vec<String> = tags.iter().map(|(name, _)| name.clone()).collect();
+    let canonical = canonical_tag_names(pool, &raw_names).await?;
+
+    // Different aliases of one tag end up as the same name, the last stance given wins
+    let mut positions: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    let mut names: Vec<String> = Vec::new();
+    let mut stances: Vec<Option<i16>> = Vec::new();
+    for (name, (_, stance)) in canonical.into_iter().zip(tags) {
+        match positions.get(&name) {
+            Some(&i) => stances[i] = stance.or(stances[i]),
+            None => {
+                positions.insert(name.clone(), names.len());
+                names.push(name);
+                stances.push(*stance);
+            }
+        }
+    }
+
+    let mut tx = pool.begin().await?;
+
+    // Serialize replacements of the same user's tags
+    sqlx::query!("SELECT id FROM Users WHERE id = $1 FOR UPDATE", user_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    for name in &names {
+        sqlx::query!(
+            r#"
+            INSERT INTO Tags (name, embedding, embedding_provider)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (name) DO UPDATE SET embedding = $2, embedding_provider = $3
+            WHERE Tags.embedding IS NULL OR Tags.embedding_provider IS DISTINCT FROM $3
+            "#,
+            name,
+            embedder.embed(name) as Vector,
+            embedder.name()
+        )
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    sqlx::query!(
+        "DELETE FROM UserTags WHERE user_id = $1 AND NOT (tag_name = ANY($2))",
+        user_id,
+        &names
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO UserTags (user_id, tag_name, stance)
+        SELECT $1, t.name, COALESCE(t.stance, 1)
+        FROM UNNEST($2::text[], $3::int2[]) AS t(name, stance)
+        ON CONFLICT (user_id, tag_name) DO NOTHING
+        "#,
+        user_id,
+        &names,
+        &stances as &[Option<i16>]
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    // Tags the user already had only change stance when one is given
+    sqlx::query!(
+        r#"
+        UPDATE UserTags ut
+        SET stance = t.stance
+        FROM UNNEST($2::text[], $3::int2[]) AS t(name, stance)
+        WHERE ut.user_id = $1 AND ut.tag_name = t.name AND t.stance IS NOT NULL
+        "#,
+        user_id,
+        &names,
+        &stances as &[Option<i16>]
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    let _ = update_user_embedding(pool, embedder, user_id).await;
+
+    get_user_tags(pool, user_id).await
+}
+
+pub async fn remove_user_tag(
+    pool: &PgPool,
+    embedder: &dyn EmbeddingProvider,
+    user_id: &str,
+    names: &[String],
+) -> Result<()> {
+    let names = canonical_tag_names(pool, names).await?;
+
     sqlx::query!(
         r#"
         DELETE FROM UserTags
         WHERE user_id = $1 AND tag_name = ANY($2)
         "#,
         user_id,
-        names
+        &names
     )
     .execute(pool)
     .await?;
 
-    // Refresh the materialized view
-    sqlx::query!("REFRESH MATERIALIZED VIEW tag_counts")
-        .execute(pool)
-        .await?;
-
     // Update user's embedding after tag change
-    let _ = update_user_embedding(pool, user_id).await;
+    let _ = update_user_embedding(pool, embedder, user_id).await;
 
     Ok(())
 }
 
 // Relationship queries
-pub async fn add_like(pool: &PgPool, user_id: &str, target_user_id: &str) -> Result<UserLike> {
-    let result = sqlx::query_as!(
+
+// Order two user IDs the way Matches stores them. Rust compares strings bytewise, which is
+// what the "C" collation in the Matches CHECK constraint does.
+fn match_pair<'a>(user_id: &'a str, other_user_id: &'a str) -> (&'a str, &'a str) {
+    if user_id < other_user_id {
+        (user_id, other_user_id)
+    } else {
+        (other_user_id, user_id)
+    }
+}
+
+// Like a user, returning the like and whether it completed a mutual match
+pub async fn add_like(
+    pool: &PgPool,
+    user_id: &str,
+    target_user_id: &str,
+) -> Result<(UserLike, bool)> {
+    let (user_a_id, user_b_id) = match_pair(user_id, target_user_id);
+
+    let mut tx = pool.begin().await?;
+
+    // Serialize likes between the same pair so two simultaneous likes can't both miss each other
+    sqlx::query!(
+        "SELECT pg_advisory_xact_lock(hashtext($1 || ':' || $2))",
+        user_a_id,
+        user_b_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let like = sqlx::query_as!(
         UserLike,
         r#"
-        INSERT INTO UserLikes (user_id, target_user_id)
-        VALUES ($1, $2)
-        ON CONFLICT (user_id, target_user_id) DO NOTHING
-        RETURNING *
+        INSERT INTO UserLikes (user_id, target_user_id)
+        VALUES ($1, $2)
+        ON CONFLICT (user_id, target_user_id) DO NOTHING
+        RETURNING *
+        "#,
+        user_id,
+        target_user_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    // If the target already liked us back, record the match
+    let matched = sqlx::query!(
+        r#"
+        INSERT INTO Matches (user_a_id, user_b_id)
+        SELECT $1, $2
+        WHERE EXISTS (
+            SELECT 1 FROM UserLikes WHERE user_id = $4 AND target_user_id = $3
+        )
+        ON CONFLICT (user_a_id, user_b_id) DO NOTHING
+        RETURNING id
+        "#,
+        user_a_id,
+        user_b_id,
+        user_id,
+        target_user_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?
+    .is_some();
+
+    tx.commit().await?;
+
+    Ok((like, matched))
+}
+
+// Remove a like and dissolve the match that depended on it, returning whether a like existed
+pub async fn remove_like(pool: &PgPool, user_id: &str, target_user_id: &str) -> Result<bool> {
+    let (user_a_id, user_b_id) = match_pair(user_id, target_user_id);
+
+    let mut tx = pool.begin().await?;
+
+    // Same lock as add_like, so an unlike can't race a like completing the match
+    sqlx::query!(
+        "SELECT pg_advisory_xact_lock(hashtext($1 || ':' || $2))",
+        user_a_id,
+        user_b_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let removed = sqlx::query!(
+        r#"
+        DELETE FROM UserLikes
+        WHERE user_id = $1 AND target_user_id = $2
+        "#,
+        user_id,
+        target_user_id
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected()
+        > 0;
+
+    sqlx::query!(
+        r#"
+        DELETE FROM Matches
+        WHERE user_a_id = $1 AND user_b_id = $2
+        "#,
+        user_a_id,
+        user_b_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(removed)
+}
+
+pub async fn get_matches(
+    pool: &PgPool,
+    user_id: &str,
+    limit: i64,
+    offset: i64,
+    cursor: Option<&Cursor<sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>>>,
+) -> Result<
+    Vec<(
+        User,
+        sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>,
+    )>,
+> {
+    let results = sqlx::query!(
+        r#"
+        SELECT u.id, u.username, u.display_name, u.avatar_url as "avatar_url: Url", u.bio, u.created_at, u.updated_at, u.embedding as "embedding: Vector", m.created_at as matched_at
+        FROM Matches m
+        JOIN Users u ON u.id = CASE WHEN m.user_a_id = $1 THEN m.user_b_id ELSE m.user_a_id END
+        WHERE (m.user_a_id = $1 OR m.user_b_id = $1)
+            AND ($4::timestamptz IS NULL OR (m.created_at, u.id) < ($4::timestamptz, $5::text))
+            AND user_is_active(u)
+            AND NOT EXISTS (
+                SELECT 1 FROM UserBlocks b
+                WHERE (b.user_id = $1 AND b.target_user_id = u.id)
+                    OR (b.user_id = u.id AND b.target_user_id = $1)
+            )
+        ORDER BY m.created_at DESC, u.id DESC
+        LIMIT $2
+        OFFSET $3
         "#,
         user_id,
-        target_user_id
+        limit,
+        offset,
+        cursor.map(|c| c.key),
+        cursor.map(|c| c.id.as_str()),
     )
-    .fetch_one(pool)
+    .fetch_all(pool)
     .await?;
 
-    Ok(result)
+    Ok(results
+        .into_iter()
+        .map(|v| {
+            (
+                User {
+                    id: v.id,
+                    username: v.username,
+                    display_name: v.display_name,
+                    avatar_url: v.avatar_url,
+                    bio: v.bio,
+                    embedding: v.embedding,
+                    created_at: v.created_at,
+                    updated_at: v.updated_at,
+                },
+                v.matched_at,
+            )
+        })
+        .collect())
 }
 
 pub async fn add_dislike(
@@ -281,12 +1525,48 @@ pub async fn add_dislike(
     Ok(result)
 }
 
+// Remove a dislike along with the tags disliked on that user, returning whether a dislike existed
+pub async fn remove_dislike(pool: &PgPool, user_id: &str, target_user_id: &str) -> Result<bool> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query!(
+        r#"
+        DELETE FROM UserDislikeTags
+        WHERE user_id = $1 AND target_user_id = $2
+        "#,
+        user_id,
+        target_user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let removed = sqlx::query!(
+        r#"
+        DELETE FROM UserDislikes
+        WHERE user_id = $1 AND target_user_id = $2
+        "#,
+        user_id,
+        target_user_id
+    )
+    .execute(&mut *tx)
+    .await?
+    .rows_affected()
+        > 0;
+
+    tx.commit().await?;
+
+    Ok(removed)
+}
+
 pub async fn add_dislike_tag(
     pool: &PgPool,
     user_id: &str,
     target_user_id: &str,
     tag_name: &str,
 ) -> Result<UserDislikeTag> {
+    let tag_name = canonical_tag_name(pool, tag_name).await?;
+    let tag_name = tag_name.as_str();
+
     // First ensure the tag exists in the Tags table
     sqlx::query!(
         r#"
@@ -323,6 +1603,7 @@ pub async fn get_liked_users(
     user_id: &str,
     limit: i64,
     offset: i64,
+    cursor: Option<&Cursor<sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>>>,
 ) -> Result<
     Vec<(
         User,
@@ -335,13 +1616,22 @@ pub async fn get_liked_users(
         FROM Users u
         JOIN UserLikes l ON u.id = l.target_user_id
         WHERE l.user_id = $1
-        ORDER BY l.created_at DESC
+            AND ($4::timestamptz IS NULL OR (l.created_at, u.id) < ($4::timestamptz, $5::text))
+            AND user_is_active(u)
+            AND NOT EXISTS (
+                SELECT 1 FROM UserBlocks b
+                WHERE (b.user_id = $1 AND b.target_user_id = u.id)
+                    OR (b.user_id = u.id AND b.target_user_id = $1)
+            )
+        ORDER BY l.created_at DESC, u.id DESC
         LIMIT $2
         OFFSET $3
         "#,
         user_id,
         limit,
         offset,
+        cursor.map(|c| c.key),
+        cursor.map(|c| c.id.as_str()),
     )
     .fetch_all(pool)
     .await?;
@@ -371,6 +1661,7 @@ pub async fn get_disliked_users(
     user_id: &str,
     limit: i64,
     offset: i64,
+    cursor: Option<&Cursor<sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>>>,
 ) -> Result<
     Vec<(
         User,
@@ -383,13 +1674,22 @@ pub async fn get_disliked_users(
         FROM Users u
         JOIN UserDislikes d ON u.id = d.target_user_id
         WHERE d.user_id = $1
-        ORDER BY d.created_at DESC
+            AND ($4::timestamptz IS NULL OR (d.created_at, u.id) < ($4::timestamptz, $5::text))
+            AND user_is_active(u)
+            AND NOT EXISTS (
+                SELECT 1 FROM UserBlocks b
+                WHERE (b.user_id = $1 AND b.target_user_id = u.id)
+                    OR (b.user_id = u.id AND b.target_user_id = $1)
+            )
+        ORDER BY d.created_at DESC, u.id DESC
         LIMIT $2
         OFFSET $3
         "#,
         user_id,
         limit,
-        offset
+        offset,
+        cursor.map(|c| c.key),
+        cursor.map(|c| c.id.as_str()),
     )
     .fetch_all(pool)
     .await?;
@@ -414,6 +1714,131 @@ pub async fn get_disliked_users(
         .collect())
 }
 
+// Block a user, dropping likes in both directions and any match between the two users
+pub async fn add_block(pool: &PgPool, user_id: &str, target_user_id: &str) -> Result<UserBlock> {
+    let (user_a_id, user_b_id) = match_pair(user_id, target_user_id);
+
+    let mut tx = pool.begin().await?;
+
+    // Same lock as add_like, so a like can't recreate the match while blocking
+    sqlx::query!(
+        "SELECT pg_advisory_xact_lock(hashtext($1 || ':' || $2))",
+        user_a_id,
+        user_b_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    // Blocking twice keeps the original block
+    let block = sqlx::query_as!(
+        UserBlock,
+        r#"
+        INSERT INTO UserBlocks (user_id, target_user_id)
+        VALUES ($1, $2)
+        ON CONFLICT (user_id, target_user_id) DO UPDATE SET created_at = UserBlocks.created_at
+        RETURNING *
+        "#,
+        user_id,
+        target_user_id
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        DELETE FROM UserLikes
+        WHERE (user_id = $1 AND target_user_id = $2) OR (user_id = $2 AND target_user_id = $1)
+        "#,
+        user_id,
+        target_user_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query!(
+        r#"
+        DELETE FROM Matches
+        WHERE user_a_id = $1 AND user_b_id = $2
+        "#,
+        user_a_id,
+        user_b_id
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(block)
+}
+
+// Unblock a user, returning whether a block existed
+pub async fn remove_block(pool: &PgPool, user_id: &str, target_user_id: &str) -> Result<bool> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM UserBlocks
+        WHERE user_id = $1 AND target_user_id = $2
+        "#,
+        user_id,
+        target_user_id
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn get_blocked_users(
+    pool: &PgPool,
+    user_id: &str,
+    limit: i64,
+    offset: i64,
+    cursor: Option<&Cursor<sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>>>,
+) -> Result<
+    Vec<(
+        User,
+        sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>,
+    )>,
+> {
+    let results = sqlx::query!(
+        r#"
+        SELECT u.id, u.username, u.display_name, u.avatar_url as "avatar_url: Url", u.bio, u.created_at, u.updated_at, u.embedding as "embedding: Vector", b.created_at as blocked_at
+        FROM Users u
+        JOIN UserBlocks b ON u.id = b.target_user_id
+        WHERE b.user_id = $1
+            AND ($4::timestamptz IS NULL OR (b.created_at, u.id) < ($4::timestamptz, $5::text))
+        ORDER BY b.created_at DESC, u.id DESC
+        LIMIT $2
+        OFFSET $3
+        "#,
+        user_id,
+        limit,
+        offset,
+        cursor.map(|c| c.key),
+        cursor.map(|c| c.id.as_str()),
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(results
+        .into_iter()
+        .map(|v| {
+            (
+                User {
+                    id: v.id,
+                    username: v.username,
+                    display_name: v.display_name,
+                    avatar_url: v.avatar_url,
+                    bio: v.bio,
+                    embedding: v.embedding,
+                    created_at: v.created_at,
+                    updated_at: v.updated_at,
+                },
+                v.blocked_at,
+            )
+        })
+        .collect())
+}
+
 pub async fn get_dislike_tags(
     pool: &PgPool,
     user_id: &str,
@@ -433,16 +1858,241 @@ pub async fn get_dislike_tags(
     .fetch_all(pool)
     .await?;
 
-    Ok(tags)
+    Ok(tags)
+}
+
+// Data export
+
+// Every like by the user with the liked user's username, newest first. Unlike
+// get_liked_users, users blocked either way are kept, an export holds everything stored.
+pub async fn get_user_likes_for_export(
+    pool: &PgPool,
+    user_id: &str,
+) -> Result<
+    Vec<(
+        String,
+        String,
+        sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>,
+    )>,
+> {
+    let results = sqlx::query!(
+        r#"
+        SELECT l.target_user_id, u.username, l.created_at
+        FROM UserLikes l
+        JOIN Users u ON u.id = l.target_user_id
+        WHERE l.user_id = $1
+        ORDER BY l.created_at DESC, l.target_user_id DESC
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(results
+        .into_iter()
+        .map(|v| (v.target_user_id, v.username, v.created_at))
+        .collect())
+}
+
+// Every dislike by the user with the disliked user's username, newest first, blocks included
+pub async fn get_user_dislikes_for_export(
+    pool: &PgPool,
+    user_id: &str,
+) -> Result<
+    Vec<(
+        String,
+        String,
+        sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>,
+    )>,
+> {
+    let results = sqlx::query!(
+        r#"
+        SELECT d.target_user_id, u.username, d.created_at
+        FROM UserDislikes d
+        JOIN Users u ON u.id = d.target_user_id
+        WHERE d.user_id = $1
+        ORDER BY d.created_at DESC, d.target_user_id DESC
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(results
+        .into_iter()
+        .map(|v| (v.target_user_id, v.username, v.created_at))
+        .collect())
+}
+
+// Every block by the user with the blocked user's username, newest first
+pub async fn get_user_blocks_for_export(
+    pool: &PgPool,
+    user_id: &str,
+) -> Result<
+    Vec<(
+        String,
+        String,
+        sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>,
+    )>,
+> {
+    let results = sqlx::query!(
+        r#"
+        SELECT b.target_user_id, u.username, b.created_at
+        FROM UserBlocks b
+        JOIN Users u ON u.id = b.target_user_id
+        WHERE b.user_id = $1
+        ORDER BY b.created_at DESC, b.target_user_id DESC
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(results
+        .into_iter()
+        .map(|v| (v.target_user_id, v.username, v.created_at))
+        .collect())
+}
+
+// Every tag the user gave as a reason to dislike someone, blocks included
+pub async fn get_user_dislike_tags_for_export(
+    pool: &PgPool,
+    user_id: &str,
+) -> Result<Vec<UserDislikeTag>> {
+    let tags = sqlx::query_as!(
+        UserDislikeTag,
+        r#"
+        SELECT *
+        FROM UserDislikeTags
+        WHERE user_id = $1
+        ORDER BY created_at DESC
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(tags)
+}
+
+// Ranking strategies
+
+// A strategy for ranking potential nemeses.
+// find_nemesis.sql exposes every score component and blends them with the weights and
+// fallback the ranker hands it, so a new strategy never needs to touch the SQL.
+pub trait NemesisRanker: Send + Sync {
+    fn strategy(&self) -> RankingStrategy;
+
+    // Weights used to blend the score components
+    fn weights(&self) -> NemesisWeights;
+
+    // Value used for components that can't be computed
+    fn fallback_score(&self) -> f64;
+}
+
+// Blend every component with configured weights
+pub struct BlendedRanker {
+    weights: NemesisWeights,
+    fallback_score: f64,
+}
+
+impl NemesisRanker for BlendedRanker {
+    fn strategy(&self) -> RankingStrategy {
+        RankingStrategy::Blended
+    }
+
+    fn weights(&self) -> NemesisWeights {
+        self.weights
+    }
+
+    fn fallback_score(&self) -> f64 {
+        self.fallback_score
+    }
+}
+
+// Only the user embeddings count, disliked tags are still penalized with the configured weight
+pub struct EmbeddingRanker {
+    weights: NemesisWeights,
+    fallback_score: f64,
+}
+
+impl NemesisRanker for EmbeddingRanker {
+    fn strategy(&self) -> RankingStrategy {
+        RankingStrategy::Embedding
+    }
+
+    fn weights(&self) -> NemesisWeights {
+        NemesisWeights {
+            user_embedding: 1.0,
+            tag_embedding: 0.0,
+            tag_overlap: 0.0,
+            ..self.weights
+        }
+    }
+
+    fn fallback_score(&self) -> f64 {
+        self.fallback_score
+    }
+}
+
+// Only tags count, split between tag embedding opposition and tag overlap. The configured
+// dislike penalty and category weights still apply
+pub struct TagRanker {
+    weights: NemesisWeights,
+    fallback_score: f64,
+}
+
+impl NemesisRanker for TagRanker {
+    fn strategy(&self) -> RankingStrategy {
+        RankingStrategy::Tags
+    }
+
+    fn weights(&self) -> NemesisWeights {
+        NemesisWeights {
+            user_embedding: 0.0,
+            tag_embedding: 0.6,
+            tag_overlap: 0.4,
+            ..self.weights
+        }
+    }
+
+    fn fallback_score(&self) -> f64 {
+        self.fallback_score
+    }
+}
+
+// Build the ranker selected by the configuration
+pub fn ranker_for(config: &RankingConfig) -> Box<dyn NemesisRanker> {
+    let fallback_score = config.fallback_score;
+
+    match config.strategy {
+        RankingStrategy::Blended => Box::new(BlendedRanker {
+            weights: config.weights,
+            fallback_score,
+        }),
+        RankingStrategy::Embedding => Box::new(EmbeddingRanker {
+            weights: config.weights,
+            fallback_score,
+        }),
+        RankingStrategy::Tags => Box::new(TagRanker {
+            weights: config.weights,
+            fallback_score,
+        }),
+    }
 }
 
 // Discovery queries
+#[allow(clippy::too_many_arguments)]
 pub async fn get_potential_nemeses(
     pool: &PgPool,
+    embedder: &dyn EmbeddingProvider,
+    ranker: &dyn NemesisRanker,
+    filter: &NemesisFilter,
     user_id: &str,
     limit: i64,
     offset: i64,
-) -> Result<Vec<(User, f64)>> {
+    cursor: Option<&Cursor<f64>>,
+) -> Result<Vec<(User, f64, ScoreComponents)>> {
     // First, get the current user's embedding
     let current_user = get_user_by_id(pool, user_id)
         .await?
@@ -456,7 +2106,7 @@ pub async fn get_potential_nemeses(
         None => {
             // Generate and update embedding
             let embedding = generate_random_embedding();
-            update_user_embedding(pool, user_id).await?;
+            update_user_embedding(pool, embedder, user_id).await?;
             embedding
         }
     };
@@ -484,6 +2134,12 @@ pub async fn get_potential_nemeses(
         }
     }
 
+    let weights = ranker.weights();
+    let require_tags: Vec<String> = filter.require_tags.iter().map(|t| t.to_string()).collect();
+    let require_tags = canonical_tag_names(pool, &require_tags).await?;
+    let exclude_tags: Vec<String> = filter.exclude_tags.iter().map(|t| t.to_string()).collect();
+    let exclude_tags = canonical_tag_names(pool, &exclude_tags).await?;
+
     // This query finds users based on user embedding similarity, tag embedding similarity, and tag overlap
     // Higher score = better nemesis match (more opposite)
     let results = sqlx::query_file!(
@@ -491,7 +2147,19 @@ pub async fn get_potential_nemeses(
         user_id,
         limit,
         offset,
-        Vector::from(opposite_embedding) as Vector
+        Vector::from(opposite_embedding) as Vector,
+        cursor.map(|c| c.key),
+        cursor.map(|c| c.id.as_str()),
+        weights.user_embedding,
+        weights.tag_embedding,
+        weights.tag_overlap,
+        weights.dislike_tag_penalty,
+        ranker.fallback_score(),
+        &require_tags,
+        &exclude_tags,
+        filter.min_score,
+        weights.same_category,
+        weights.cross_category
     )
     .fetch_all(pool)
     .await?;
@@ -511,20 +2179,576 @@ pub async fn get_potential_nemeses(
                     updated_at: v.updated_at,
                 },
                 v.compatibility_score.unwrap_or(0.0),
+                ScoreComponents {
+                    user_embedding: v.user_embedding_score.unwrap_or(ranker.fallback_score()),
+                    tag_embedding: v.tag_embedding_score.unwrap_or(ranker.fallback_score()),
+                    tag_overlap: v.tag_overlap_score.unwrap_or(ranker.fallback_score()),
+                    dislike_tag_penalty: v.dislike_tag_penalty.unwrap_or(0.0),
+                },
             )
         })
         .collect())
 }
 
+// Find the most strongly opposed (user tag, candidate tag) pairs between a user and each
+// candidate, keyed by candidate id. Opposition is weighted by category as in the nemesis score.
+// Candidates without tag embeddings are left out.
+pub async fn get_opposing_tag_pairs(
+    pool: &PgPool,
+    user_id: &str,
+    candidate_ids: &[String],
+    weights: &NemesisWeights,
+    limit: i64,
+) -> Result<std::collections::HashMap<String, Vec<OpposingTagPair>>> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT candidate_id as "candidate_id!", tag_name as "tag_name!", opposing_tag_name as "opposing_tag_name!", opposition as "opposition!"
+        FROM (
+            SELECT
+                other_user_id as candidate_id,
+                tag_name,
+                other_tag_name as opposing_tag_name,
+                opposition * weight as opposition,
+                ROW_NUMBER() OVER (
+                    PARTITION BY other_user_id
+                    ORDER BY opposition * weight DESC, tag_name, other_tag_name
+                ) as rank
+            FROM (
+                SELECT
+                    uto.*,
+                    CASE WHEN uto.same_category THEN $3::float8 ELSE $4::float8 END as weight
+                FROM user_tag_oppositions uto
+                WHERE uto.user_id = $1 AND uto.other_user_id = ANY($2)
+            ) weighted
+        ) pairs
+        WHERE rank <= $5
+        ORDER BY candidate_id, rank
+        "#,
+        user_id,
+        candidate_ids,
+        weights.same_category,
+        weights.cross_category,
+        limit
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut pairs: std::collections::HashMap<String, Vec<OpposingTagPair>> =
+        std::collections::HashMap::new();
+    for row in rows {
+        pairs
+            .entry(row.candidate_id)
+            .or_default()
+            .push(OpposingTagPair {
+                tag_name: row.tag_name,
+                opposing_tag_name: row.opposing_tag_name,
+                opposition: row.opposition,
+            });
+    }
+
+    Ok(pairs)
+}
+
+// Check whether a user has the admin role
+pub async fn is_admin(pool: &PgPool, user_id: &str) -> Result<bool> {
+    let result = sqlx::query!(
+        r#"SELECT role = 'admin' as "is_admin!" FROM Users WHERE id = $1"#,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(result.map(|v| v.is_admin).unwrap_or(false))
+}
+
+// Give a user the admin role, only used to set up admins for integration tests
+#[cfg(feature = "dummy-auth")]
+pub async fn grant_admin(pool: &PgPool, user_id: &str) -> Result<()> {
+    sqlx::query!("UPDATE Users SET role = 'admin' WHERE id = $1", user_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+// Moderation queries
+
+// File a report, returns None if the reporter already has a pending report against the user
+pub async fn create_report(
+    pool: &PgPool,
+    reporter_id: &str,
+    reported_user_id: &str,
+    reason: ReportReason,
+    details: &str,
+) -> Result<Option<Report>> {
+    let report = sqlx::query_as!(
+        Report,
+        r#"
+        INSERT INTO Reports (reporter_id, reported_user_id, reason, details)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (reporter_id, reported_user_id) WHERE status IN ('open', 'triaged') DO NOTHING
+        RETURNING id, reporter_id, reported_user_id, reason as "reason: ReportReason", details,
+            status as "status: ReportStatus", action as "action: ModerationAction",
+            moderator_notes, handled_by, created_at, updated_at, resolved_at
+        "#,
+        reporter_id,
+        reported_user_id,
+        reason as _,
+        details
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(report)
+}
+
+// List reports oldest first, optionally only those with the given status
+pub async fn get_reports(
+    pool: &PgPool,
+    status: Option<ReportStatus>,
+    limit: i64,
+    offset: i64,
+    cursor: Option<(sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>, i32)>,
+) -> Result<Vec<Report>> {
+    let reports = sqlx::query_as!(
+        Report,
+        r#"
+        SELECT id, reporter_id, reported_user_id, reason as "reason: ReportReason", details,
+            status as "status: ReportStatus", action as "action: ModerationAction",
+            moderator_notes, handled_by, created_at, updated_at, resolved_at
+        FROM Reports
+        WHERE ($1::varchar IS NULL OR status = $1)
+            AND ($4::timestamptz IS NULL OR (created_at, id) > ($4::timestamptz, $5::int))
+        ORDER BY created_at ASC, id ASC
+        LIMIT $2
+        OFFSET $3
+        "#,
+        status as _,
+        limit,
+        offset,
+        cursor.map(|(created_at, _)| created_at),
+        cursor.map(|(_, id)| id),
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(reports)
+}
+
+pub async fn get_report(pool: &PgPool, id: i32) -> Result<Option<Report>> {
+    let report = sqlx::query_as!(
+        Report,
+        r#"
+        SELECT id, reporter_id, reported_user_id, reason as "reason: ReportReason", details,
+            status as "status: ReportStatus", action as "action: ModerationAction",
+            moderator_notes, handled_by, created_at, updated_at, resolved_at
+        FROM Reports
+        WHERE id = $1
+        "#,
+        id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(report)
+}
+
+// Lock a report until the end of the transaction, so concurrent moderators act on it one at a time
+async fn lock_report(tx: &mut sqlx::Transaction<'_, sqlx::Postgres>, id: i32) -> Result<Report> {
+    let report = sqlx::query_as!(
+        Report,
+        r#"
+        SELECT id, reporter_id, reported_user_id, reason as "reason: ReportReason", details,
+            status as "status: ReportStatus", action as "action: ModerationAction",
+            moderator_notes, handled_by, created_at, updated_at, resolved_at
+        FROM Reports
+        WHERE id = $1
+        FOR UPDATE
+        "#,
+        id
+    )
+    .fetch_optional(&mut **tx)
+    .await?
+    .ok_or_else(|| Error::NotFound {
+        resource: format!("Report with ID {}", id),
+    })?;
+
+    Ok(report)
+}
+
+// Move a report through the queue, keeping the previous status or notes when not given.
+// Reports that were acted on keep their status.
+pub async fn update_report(
+    pool: &PgPool,
+    id: i32,
+    admin_id: &str,
+    status: Option<ReportStatus>,
+    moderator_notes: Option<&str>,
+) -> Result<Report> {
+    let mut tx = pool.begin().await?;
+
+    let report = lock_report(&mut tx, id).await?;
+    if report.action.is_some() && status.is_some_and(|s| s != report.status) {
+        return Err(Error::Validation {
+            field: "status".to_string(),
+            message: "Report was already acted on".to_string(),
+        });
+    }
+
+    let result = sqlx::query_as!(
+        Report,
+        r#"
+        UPDATE Reports
+        SET status = COALESCE($3, status),
+            moderator_notes = COALESCE($4, moderator_notes),
+            handled_by = $2,
+            updated_at = NOW(),
+            resolved_at = CASE
+                WHEN COALESCE($3, status) IN ('resolved', 'dismissed') THEN COALESCE(resolved_at, NOW())
+                ELSE NULL
+            END
+        WHERE id = $1
+        RETURNING id, reporter_id, reported_user_id, reason as "reason: ReportReason", details,
+            status as "status: ReportStatus", action as "action: ModerationAction",
+            moderator_notes, handled_by, created_at, updated_at, resolved_at
+        "#,
+        id,
+        admin_id,
+        status as _,
+        moderator_notes
+    )
+    .fetch_one(&mut *tx)
+    .await;
+
+    // Reopening a report is refused while the reporter has another pending report on the same user
+    let report = match result {
+        Ok(report) => report,
+        Err(sqlx::Error::Database(ref db)) if db.is_unique_violation() => {
+            return Err(Error::Duplicate {
+                resource: format!(
+                    "Pending report by {} against {}",
+                    report.reporter_id.as_deref().unwrap_or("a deleted user"),
+                    report
+                        .reported_user_id
+                        .as_deref()
+                        .unwrap_or("a deleted user")
+                ),
+            });
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    tx.commit().await?;
+
+    Ok(report)
+}
+
+// Act on a pending report against its reported user and resolve it.
+// `message` is the warning shown to the user, `suspended_until` is only used for suspensions
+// and `grace_period` for deletions.
+pub async fn action_report(
+    pool: &PgPool,
+    id: i32,
+    admin_id: &str,
+    action: ModerationAction,
+    message: &str,
+    suspended_until: sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>,
+    grace_period: std::time::Duration,
+) -> Result<Report> {
+    let mut tx = pool.begin().await?;
+
+    // A report acted on by another moderator in the meantime is no longer pending
+    let report = lock_report(&mut tx, id).await?;
+    if !report.status.is_pending() {
+        return Err(Error::Validation {
+            field: "status".to_string(),
+            message: "Only pending reports can be acted on".to_string(),
+        });
+    }
+
+    let reported_user_id = report
+        .reported_user_id
+        .as_deref()
+        .ok_or_else(|| Error::NotFound {
+            resource: format!("Reported user of report {}", report.id),
+        })?;
+
+    match action {
+        ModerationAction::Warn => {
+            sqlx::query!(
+                r#"
+                INSERT INTO UserWarnings (user_id, report_id, message)
+                VALUES ($1, $2, $3)
+                "#,
+                reported_user_id,
+                report.id,
+                message
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        ModerationAction::Suspend => {
+            sqlx::query!(
+                r#"
+                UPDATE Users
+                SET suspended_until = GREATEST(suspended_until, $2)
+                WHERE id = $1
+                "#,
+                reported_user_id,
+                suspended_until
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            sqlx::query!(
+                r#"
+                INSERT INTO UserWarnings (user_id, report_id, message)
+                VALUES ($1, $2, $3)
+                "#,
+                reported_user_id,
+                report.id,
+                message
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+        ModerationAction::Delete => {
+            // Scheduled like a deletion by the user, so purge_deleted_users removes their
+            // uploads along with the account. They stay suspended until then.
+            sqlx::query!(
+                r#"
+                UPDATE Users
+                SET deletion_scheduled_at = COALESCE(deletion_scheduled_at, NOW() + make_interval(secs => $2)),
+                    suspended_until = GREATEST(
+                        suspended_until,
+                        COALESCE(deletion_scheduled_at, NOW() + make_interval(secs => $2))
+                    )
+                WHERE id = $1
+                "#,
+                reported_user_id,
+                grace_period.as_secs_f64()
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            sqlx::query!(
+                r#"
+                INSERT INTO UserWarnings (user_id, report_id, message)
+                VALUES ($1, $2, $3)
+                "#,
+                reported_user_id,
+                report.id,
+                message
+            )
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+
+    let report = sqlx::query_as!(
+        Report,
+        r#"
+        UPDATE Reports
+        SET status = 'resolved',
+            action = $3,
+            handled_by = $2,
+            updated_at = NOW(),
+            resolved_at = NOW()
+        WHERE id = $1
+        RETURNING id, reporter_id, reported_user_id, reason as "reason: ReportReason", details,
+            status as "status: ReportStatus", action as "action: ModerationAction",
+            moderator_notes, handled_by, created_at, updated_at, resolved_at
+        "#,
+        report.id,
+        admin_id,
+        action as _
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(report)
+}
+
+// Get the end of a user's suspension, if they are currently suspended
+pub async fn get_active_suspension(
+    pool: &PgPool,
+    user_id: &str,
+) -> Result<Option<sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>>> {
+    let result = sqlx::query!(
+        r#"
+        SELECT suspended_until as "suspended_until!"
+        FROM Users
+        WHERE id = $1 AND suspended_until > NOW()
+        "#,
+        user_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(result.map(|v| v.suspended_until))
+}
+
+pub async fn get_user_warnings(pool: &PgPool, user_id: &str) -> Result<Vec<UserWarning>> {
+    let warnings = sqlx::query_as!(
+        UserWarning,
+        r#"
+        SELECT *
+        FROM UserWarnings
+        WHERE user_id = $1
+        ORDER BY created_at DESC
+        "#,
+        user_id
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(warnings)
+}
+
+// Account deletion
+
+// Schedule the user's account for deletion once `grace_period` has passed. Scheduling again
+// keeps the original time. Returns when the account will be deleted.
+pub async fn schedule_user_deletion(
+    pool: &PgPool,
+    user_id: &str,
+    grace_period: std::time::Duration,
+) -> Result<sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE Users
+        SET deletion_scheduled_at = COALESCE(deletion_scheduled_at, NOW() + make_interval(secs => $2))
+        WHERE id = $1
+        RETURNING deletion_scheduled_at as "deletion_scheduled_at!"
+        "#,
+        user_id,
+        grace_period.as_secs_f64()
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(result.deletion_scheduled_at)
+}
+
+pub async fn get_user_deletion(
+    pool: &PgPool,
+    user_id: &str,
+) -> Result<Option<sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>>> {
+    let result = sqlx::query_scalar!(
+        "SELECT deletion_scheduled_at FROM Users WHERE id = $1",
+        user_id
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(result.flatten())
+}
+
+// Cancel a scheduled deletion, returning whether one was pending. A deletion ordered by a
+// moderator can't be cancelled.
+pub async fn cancel_user_deletion(pool: &PgPool, user_id: &str) -> Result<bool> {
+    let result = sqlx::query!(
+        r#"
+        UPDATE Users
+        SET deletion_scheduled_at = NULL
+        WHERE id = $1 AND deletion_scheduled_at IS NOT NULL
+            AND NOT EXISTS (
+                SELECT 1 FROM Reports WHERE reported_user_id = $1 AND action = 'delete'
+            )
+        "#,
+        user_id
+    )
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() > 0 {
+        return Ok(true);
+    }
+
+    let ordered_by_moderator = sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM Reports WHERE reported_user_id = $1 AND action = 'delete'
+        ) as "exists!"
+        "#,
+        user_id
+    )
+    .fetch_one(pool)
+    .await?;
+
+    if ordered_by_moderator {
+        return Err(Error::Forbidden {
+            reason: "The account was deleted by a moderator".to_string(),
+        });
+    }
+
+    Ok(false)
+}
+
+// Delete the accounts whose grace period is over, after removing their uploads from the
+// bucket. Everything else the user owns cascades, and tag counts follow through the UserTags
+// triggers. An account that can't be deleted is left for the next run.
+// Returns the number of accounts deleted, and the accounts left with why.
+pub async fn purge_deleted_users(
+    pool: &PgPool,
+    bucket: &Bucket,
+) -> Result<(usize, Vec<(String, Error)>)> {
+    let due: Vec<String> =
+        sqlx::query_scalar!("SELECT id FROM Users WHERE deletion_scheduled_at <= NOW()")
+            .fetch_all(pool)
+            .await?;
+
+    let mut deleted = 0;
+    let mut failed = Vec::new();
+    for user_id in due {
+        match purge_deleted_user(pool, bucket, &user_id).await {
+            Ok(true) => deleted += 1,
+            Ok(false) => {}
+            Err(e) => failed.push((user_id, e)),
+        }
+    }
+
+    Ok((deleted, failed))
+}
+
+// Delete one account and its uploads, returning whether it was still due. The row stays
+// locked until the account is gone, so the user can't cancel once their uploads are removed.
+async fn purge_deleted_user(pool: &PgPool, bucket: &Bucket, user_id: &str) -> Result<bool> {
+    let mut tx = pool.begin().await?;
+
+    // The user may have cancelled in the meantime
+    let due = sqlx::query_scalar!(
+        "SELECT id FROM Users WHERE id = $1 AND deletion_scheduled_at <= NOW() FOR UPDATE",
+        user_id
+    )
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    if due.is_none() {
+        return Ok(false);
+    }
+
+    let mut objects = storage::utils::get_user_objects(pool, user_id).await?;
+    objects.extend(storage::utils::get_untracked_avatar_object(pool, user_id).await?);
+    storage::utils::delete_objects(bucket, &objects).await?;
+
+    sqlx::query!("DELETE FROM Users WHERE id = $1", user_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(true)
+}
+
 // Helper functions
 pub fn generate_random_username() -> String {
     let uuid = Uuid::new_v4();
     format!("user_{}", uuid.simple())
 }
 
-// Constants
-const EMBEDDING_DIM: usize = 384;
-
 // Generate a random embedding vector for MVP purposes
 // This simulates a 384-dimensional embedding from all-MiniLM-L6-v2
 pub fn generate_random_embedding() -> Vector {
@@ -539,71 +2763,36 @@ pub fn generate_random_embedding() -> Vector {
         .map(|_| rng.random_range(-1.0..1.0))
         .collect();
 
-    // Calculate the magnitude of the vector
-    let magnitude: f32 = random_values
-        .iter()
-        .map(|&val| val * val)
-        .sum::<f32>()
-        .sqrt();
-
-    // Normalize the vector (divide each element by the magnitude)
-    if magnitude > 0.0 {
-        Vector::from(
-            random_values
-                .iter()
-                .map(|&val| val / magnitude)
-                .collect::<Vec<_>>(),
-        )
-    } else {
-        // Fallback in case of zero magnitude (shouldn't happen with random values)
-        Vector::from(random_values)
-    }
+    normalize(random_values)
 }
 
-// Generate a tag embedding based on the tag name
-// In a real implementation, this would use a text embedding model
-pub fn generate_tag_embedding(tag_name: &str) -> Vector {
-    use rand::rngs::StdRng;
-    use rand::{Rng, SeedableRng};
-    use std::collections::hash_map::DefaultHasher;
-    use std::hash::{Hash, Hasher};
-
-    // Create a deterministic seed from the tag name so the same tag
-    // always gets the same embedding (this is important for consistency)
-    let mut hasher = DefaultHasher::new();
-    tag_name.hash(&mut hasher);
-    let seed = hasher.finish();
-
-    // Create a deterministic random number generator
-    let mut rng = StdRng::seed_from_u64(seed);
-
-    // Generate deterministic random values
-    let random_values: Vec<f32> = (0..EMBEDDING_DIM)
-        .map(|_| rng.random_range(-1.0..1.0))
-        .collect();
-
-    // Normalize the vector
-    let magnitude: f32 = random_values
+// Average tag embeddings weighted by stance, negative stances negating their tag, normalized
+pub fn stance_centroid(tag_embeddings: &[(Vec<f32>, i16)]) -> Vector {
+    let total_weight: f32 = tag_embeddings
         .iter()
-        .map(|&val| val * val)
-        .sum::<f32>()
-        .sqrt();
+        .map(|(_, stance)| stance.unsigned_abs() as f32)
+        .sum();
 
-    if magnitude > 0.0 {
-        Vector::from(
-            random_values
-                .iter()
-                .map(|&val| val / magnitude)
-                .collect::<Vec<_>>(),
-        )
-    } else {
-        Vector::from(random_values)
+    let mut avg_embedding = vec![0.0; EMBEDDING_DIM];
+    if total_weight > 0.0 {
+        for (tag_embedding, stance) in tag_embeddings {
+            let weight = *stance as f32 / total_weight;
+            for (i, &val) in tag_embedding.iter().enumerate() {
+                avg_embedding[i] += val * weight;
+            }
+        }
     }
+
+    normalize(avg_embedding)
 }
 
 // Update a user's embedding
 // This creates an embedding based on the user's tags
-pub async fn update_user_embedding(pool: &PgPool, user_id: &str) -> Result<()> {
+pub async fn update_user_embedding(
+    pool: &PgPool,
+    embedder: &dyn EmbeddingProvider,
+    user_id: &str,
+) -> Result<()> {
     // Get all of the user's tags
     let user_tags = get_user_tags(pool, user_id).await?;
 
@@ -611,7 +2800,7 @@ pub async fn update_user_embedding(pool: &PgPool, user_id: &str) -> Result<()> {
         // If user has no tags, use a random embedding
         generate_random_embedding()
     } else {
-        // Get tag embeddings
+        // Get tag embeddings, with the stance of the user on each
         let mut tag_embeddings = Vec::new();
 
         for tag in &user_tags {
@@ -624,16 +2813,17 @@ pub async fn update_user_embedding(pool: &PgPool, user_id: &str) -> Result<()> {
             .await?;
 
             if let (Some(embedding),) = tag_embedding {
-                tag_embeddings.push(embedding.to_vec());
+                tag_embeddings.push((embedding.to_vec(), tag.stance));
             } else {
                 // If tag doesn't have an embedding, generate one
-                let generated = generate_tag_embedding(&tag.tag_name);
-                tag_embeddings.push(generated.to_vec());
+                let generated = embedder.embed(&tag.tag_name);
+                tag_embeddings.push((generated.to_vec(), tag.stance));
 
                 // Update tag with embedding
                 sqlx::query!(
-                    "UPDATE Tags SET embedding = $1 WHERE name = $2",
+                    "UPDATE Tags SET embedding = $1, embedding_provider = $2 WHERE name = $3",
                     &generated as &Vector,
+                    embedder.name(),
                     &tag.tag_name
                 )
                 .execute(pool)
@@ -641,31 +2831,9 @@ pub async fn update_user_embedding(pool: &PgPool, user_id: &str) -> Result<()> {
             }
         }
 
-        // Average the tag embeddings
-        let mut avg_embedding = vec![0.0; EMBEDDING_DIM];
-        for tag_embedding in &tag_embeddings {
-            for (i, &val) in tag_embedding.iter().enumerate() {
-                avg_embedding[i] += val / tag_embeddings.len() as f32;
-            }
-        }
-
-        // Normalize the embedding
-        let magnitude: f32 = avg_embedding
-            .iter()
-            .map(|&val| val * val)
-            .sum::<f32>()
-            .sqrt();
-
-        if magnitude > 0.0 {
-            Vector::from(
-                avg_embedding
-                    .iter()
-                    .map(|&val| val / magnitude)
-                    .collect::<Vec<_>>(),
-            )
-        } else {
-            Vector::from(avg_embedding)
-        }
+        // Tags the user is against point the other way, so users with opposite stances on
+        // the same tag end up opposite
+        stance_centroid(&tag_embeddings)
     };
 
     // Update the user's embedding in the database
@@ -683,3 +2851,45 @@ pub async fn update_user_embedding(pool: &PgPool, user_id: &str) -> Result<()> {
 
     Ok(())
 }
+
+// Re-embed tags that have no embedding or were embedded by a different provider,
+// then recompute the embeddings of every user holding one of those tags.
+// Returns the number of tags that were re-embedded.
+pub async fn reembed_stale_tags(pool: &PgPool, embedder: &dyn EmbeddingProvider) -> Result<usize> {
+    let stale_tags = sqlx::query!(
+        r#"
+        SELECT name FROM Tags
+        WHERE embedding IS NULL OR embedding_provider IS DISTINCT FROM $1
+        "#,
+        embedder.name()
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|v| v.name)
+    .collect::<Vec<_>>();
+
+    for name in &stale_tags {
+        sqlx::query!(
+            "UPDATE Tags SET embedding = $1, embedding_provider = $2 WHERE name = $3",
+            embedder.embed(name) as Vector,
+            embedder.name(),
+            name
+        )
+        .execute(pool)
+        .await?;
+    }
+
+    let affected_users = sqlx::query!(
+        "SELECT DISTINCT user_id FROM UserTags WHERE tag_name = ANY($1)",
+        &stale_tags
+    )
+    .fetch_all(pool)
+    .await?;
+
+    for user in affected_users {
+        update_user_embedding(pool, embedder, &user.user_id).await?;
+    }
+
+    Ok(stale_tags.len())
+}